@@ -0,0 +1,46 @@
+// Embed the source of every day and of the library code they share, so that
+// cached answers can be thrown away when the code that computed them
+// changes (see src/cache.rs).
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    let mut days = vec![];
+    for path in rust_files(&manifest.join("src/days")) {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        if name.starts_with("day") {
+            days.push(format!("    ({:?}, include_str!({:?})),\n", name, path));
+        }
+    }
+    let shared: Vec<String> = rust_files(&manifest.join("src"))
+        .into_iter()
+        .chain([manifest.join("src/days/mod.rs")])
+        .filter(|p| p.file_name().unwrap() != "main.rs")
+        .map(|p| format!("    include_str!({:?}),\n", p))
+        .collect();
+
+    let sources = format!(
+        "pub const DAYS: &[(&str, &str)] = &[\n{}];\n\
+         pub const SHARED: &[&str] = &[\n{}];\n",
+        days.concat(),
+        shared.concat()
+    );
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("sources.rs");
+    fs::write(out, sources).unwrap();
+}
+
+// The `.rs` files directly in `dir`, sorted so the output is stable.
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+        .collect();
+    files.sort();
+    files
+}
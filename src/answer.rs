@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// The result of solving one part of a puzzle.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// The part has no answer for this input, e.g. day 25 has no part 2.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "-"),
+        }
+    }
+}
+
+// Numbers too big for an i64 are kept as text rather than wrapping.
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    i64::try_from(v).map_or_else(|_| Answer::Text(v.to_string()), Answer::Int)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Answer::Unsolved, Into::into)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::runner::{self, Run};
+use crate::solution::Day;
+use std::time::Duration;
+
+/// How long a day may take on one input of a batch before it is flagged.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(10);
+
+/// The result of running a day against one input of a batch.
+pub enum Outcome {
+    /// The input was parsed, and each part was solved or failed, e.g. by
+    /// panicking or running out of time.
+    Ran(Run),
+    /// The input couldn't be read or parsed.
+    Error(Error),
+}
+
+impl Outcome {
+    /// Whether the input needs a closer look: it failed, or took longer
+    /// than `budget` to solve.
+    pub fn flagged(&self, budget: Duration) -> bool {
+        match self {
+            Outcome::Ran((parse_time, parts)) => {
+                let mut time = *parse_time;
+                for (_, part) in parts {
+                    match part {
+                        Ok(part) => time += part.time,
+                        Err(_) => return true,
+                    }
+                }
+                time > budget
+            }
+            Outcome::Error(_) => true,
+        }
+    }
+}
+
+/// Run `day` against every input in `paths`, with the parameters that
+/// `params` gives for each. Each part is solved in a worker process (see
+/// `worker::solve`) that is killed once it has used up the `budget`, so an
+/// input that panics or never finishes doesn't stop the rest of the batch.
+pub fn batch(
+    day: &Day,
+    paths: &[String],
+    budget: Duration,
+    mut params: impl FnMut(&str) -> Result<Params>,
+) -> Vec<Outcome> {
+    paths
+        .iter()
+        .map(|path| {
+            let run = params(path).and_then(|params| {
+                runner::run_parts(day, path, &params, &[1, 2], Some(budget), None)
+            });
+            match run {
+                Ok(run) => Outcome::Ran(run),
+                Err(e) => Outcome::Error(e),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,135 @@
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::runner;
+use crate::solution::Day;
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+pub const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Summary of repeated timings of one step.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            sorted
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Time parse, part 1 and part 2 of `day` separately, `iterations` times
+/// each. Returns the stats for each step, in the order of `STEPS`.
+pub fn bench(day: &Day, path: &str, params: &Params, iterations: usize) -> Result<Vec<Stats>> {
+    assert!(iterations > 0, "need at least one iteration");
+    let contents = runner::read_input(path)?;
+    let (input, _) = runner::parse(day, path, &contents, params)?;
+
+    let mut samples = vec![vec![]; STEPS.len()];
+    for _ in 0..iterations {
+        samples[0].push(runner::parse(day, path, &contents, params)?.1);
+        samples[1].push(runner::solve(day, &*input, 1).time);
+        samples[2].push(runner::solve(day, &*input, 2).time);
+    }
+    Ok(samples.iter().map(|s| Stats::from_samples(s)).collect())
+}
+
+/// Saved benchmark results, keyed by day name, input path and step, so
+/// that timings are only compared against the same input.
+pub type Baseline = HashMap<(String, String, String), Stats>;
+
+const HEADER: &str = "day,input,step,min_ns,median_ns,mean_ns,stddev_ns";
+
+pub fn read_baseline(path: &str) -> Result<Baseline> {
+    let contents = runner::read_input(path)?;
+    contents
+        .lines()
+        .skip_while(|l| *l == HEADER)
+        .map(|l| {
+            let cells: Vec<_> = l.split(',').collect();
+            if cells.len() != 7 {
+                return Err(
+                    Error::parse(l, "expected 7 comma separated values").locate(path, &contents)
+                );
+            }
+            let ns = |i: usize| -> Result<Duration> {
+                crate::error::number(cells[i])
+                    .map(Duration::from_nanos)
+                    .map_err(|e| e.locate(path, &contents))
+            };
+            let stats = Stats {
+                min: ns(3)?,
+                median: ns(4)?,
+                mean: ns(5)?,
+                stddev: ns(6)?,
+            };
+            let key = (
+                cells[0].to_string(),
+                cells[1].to_string(),
+                cells[2].to_string(),
+            );
+            Ok((key, stats))
+        })
+        .collect()
+}
+
+/// Write `baseline` as CSV, one row per day, input and step.
+pub fn write_baseline(path: &str, baseline: &Baseline) -> Result<()> {
+    if let Some((_, input, _)) = baseline.keys().find(|(_, input, _)| input.contains(',')) {
+        return Err(Error::Usage(format!(
+            "Can't save a baseline for {}: input paths can't contain commas",
+            input
+        )));
+    }
+    let mut keys: Vec<_> = baseline.keys().collect();
+    keys.sort_by_key(|(day, input, step)| {
+        (
+            day.trim_start_matches("day").parse::<u32>().ok(),
+            input.clone(),
+            step.clone(),
+        )
+    });
+    let mut csv = vec![HEADER.to_string()];
+    for key in keys {
+        let s = baseline[key];
+        csv.push(format!(
+            "{},{},{},{},{},{},{}",
+            key.0,
+            key.1,
+            key.2,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.stddev.as_nanos()
+        ));
+    }
+    fs::write(path, csv.join("\n") + "\n").map_err(|e| Error::Io(path.to_string(), e))
+}
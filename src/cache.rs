@@ -0,0 +1,150 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::runner::Part;
+use crate::solution::Day;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// The source of every day and of the library code they share, embedded by
+// build.rs.
+mod sources {
+    include!(concat!(env!("OUT_DIR"), "/sources.rs"));
+}
+
+/// Where the cache is kept, relative to the current directory.
+pub const DEFAULT_DIR: &str = ".aoc24-cache";
+
+/// What is known about a day's run on one input.
+#[derive(Clone, Debug, Default)]
+pub struct Entry {
+    pub parse_time: Option<Duration>,
+    pub parts: BTreeMap<u32, Part>,
+}
+
+/// Answers and timings that have already been computed, one file per day
+/// and input. The file is named after the SHA-256 of the input and its
+/// parameters, and the solver version from `version`, so that editing one
+/// day only throws away that day's answers:
+///
+/// ```toml
+/// # .aoc24-cache/day7-<input>-<version>.toml
+/// parse_ns = 84210
+///
+/// [part1]
+/// answer = 3749
+/// time_ns = 13277
+/// ```
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cached answers for `contents`. A missing or unreadable file is
+    /// an empty entry, since everything in it can be computed again.
+    pub fn load(&self, day: &Day, contents: &str, params: &Params) -> Entry {
+        fs::read_to_string(self.path(day, contents, params))
+            .ok()
+            .and_then(|s| s.parse::<toml::Table>().ok())
+            .map(|table| entry_from_toml(&table))
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self, day: &Day, contents: &str, params: &Params, entry: &Entry) -> Result<()> {
+        let path = self.path(day, contents, params);
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, entry_to_toml(entry).to_string()))
+            .map_err(|e| Error::Io(path.display().to_string(), e))
+    }
+
+    fn path(&self, day: &Day, contents: &str, params: &Params) -> PathBuf {
+        let input = sha256(&[params.to_args().join(" ").as_str(), "\n", contents]);
+        self.dir.join(format!(
+            "{}-{}-{}.toml",
+            day.name(),
+            &input[..16],
+            &version(day)[..16]
+        ))
+    }
+}
+
+/// The version of a day's solver: the SHA-256 of its source and of the
+/// library code that all of the days share.
+pub fn version(day: &Day) -> String {
+    let name = day.name();
+    let own = sources::DAYS.iter().filter(|(n, _)| *n == name);
+    let sources: Vec<&str> = own
+        .map(|(_, s)| *s)
+        .chain(sources::SHARED.iter().copied())
+        .collect();
+    sha256(&sources)
+}
+
+fn sha256(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn entry_from_toml(table: &toml::Table) -> Entry {
+    let nanos = |v: Option<&toml::Value>| {
+        v.and_then(|v| v.as_integer())
+            .map(|ns| Duration::from_nanos(ns as u64))
+    };
+    let mut entry = Entry {
+        parse_time: nanos(table.get("parse_ns")),
+        ..Entry::default()
+    };
+    for part in [1, 2] {
+        let Some(t) = table
+            .get(&format!("part{}", part))
+            .and_then(|t| t.as_table())
+        else {
+            continue;
+        };
+        let answer = match t.get("answer") {
+            Some(toml::Value::Integer(v)) => Answer::Int(*v),
+            Some(toml::Value::String(s)) => Answer::Text(s.clone()),
+            _ => Answer::Unsolved,
+        };
+        if let Some(time) = nanos(t.get("time_ns")) {
+            entry.parts.insert(part, Part { answer, time });
+        }
+    }
+    entry
+}
+
+fn entry_to_toml(entry: &Entry) -> toml::Table {
+    let mut table = toml::Table::new();
+    if let Some(parse_time) = entry.parse_time {
+        table.insert("parse_ns".to_string(), nanos(parse_time));
+    }
+    for (part, p) in &entry.parts {
+        let mut t = toml::Table::new();
+        match &p.answer {
+            Answer::Int(v) => t.insert("answer".to_string(), toml::Value::Integer(*v)),
+            Answer::Text(s) => t.insert("answer".to_string(), toml::Value::String(s.clone())),
+            Answer::Unsolved => None,
+        };
+        t.insert("time_ns".to_string(), nanos(p.time));
+        table.insert(format!("part{}", part), toml::Value::Table(t));
+    }
+    table
+}
+
+fn nanos(d: Duration) -> toml::Value {
+    toml::Value::Integer(d.as_nanos() as i64)
+}
@@ -1,38 +1,42 @@
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use itertools::Itertools;
-use std::fs;
 use std::iter::zip;
 
-pub fn day1(args: &[String]) {
-    println!("Day 1");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day1;
 
-    let (mut left, mut right): (Vec<_>, Vec<_>) = contents
-        .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|i| i.parse::<i32>().unwrap())
-                .collect_tuple()
-                .unwrap()
-        })
-        .unzip();
-    left.sort();
-    right.sort();
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+    type Part1 = i32;
+    type Part2 = i32;
 
-    let part1: i32 = zip(left.clone(), right.clone())
-        .map(|(a, b)| (a - b).abs())
-        .sum();
-    println!("Part 1: {}", part1);
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (mut left, mut right): (Vec<_>, Vec<_>) = input
+            .lines()
+            .map(|l| {
+                l.split_ascii_whitespace()
+                    .map(error::number::<i32>)
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .collect_tuple()
+                    .ok_or_else(|| Error::parse(l, "expected two location IDs"))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        left.sort();
+        right.sort();
+        Ok((left, right))
+    }
 
-    let right_counts = right.iter().counts_by(|v| v);
-    let part2: i32 = left
-        .iter()
-        .map(|l| *right_counts.get(l).unwrap_or(&0) as i32 * l)
-        .sum();
-    println!("Part 2: {}", part2);
+    fn part1((left, right): &Self::Input) -> i32 {
+        zip(left, right).map(|(a, b)| (a - b).abs()).sum()
+    }
+
+    fn part2((left, right): &Self::Input) -> i32 {
+        let right_counts = right.iter().counts_by(|v| v);
+        left.iter()
+            .map(|l| *right_counts.get(l).unwrap_or(&0) as i32 * l)
+            .sum()
+    }
 }
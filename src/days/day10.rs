@@ -1,103 +1,90 @@
-use itertools::{iproduct, Itertools};
-use std::fs;
-use std::num::ParseIntError;
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use itertools::Itertools;
 use std::str::FromStr;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct TopoMap {
-    map: Vec<Vec<u32>>,
+pub struct TopoMap {
+    map: Grid<u32>,
 }
 
 impl FromStr for TopoMap {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
+        error::chars(s, "0123456789")?;
         Ok(Self {
-            map: s
-                .lines()
-                .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-                .collect(),
+            map: Grid::parse(s)?.map(|c| c.to_digit(10).unwrap()),
         })
     }
 }
 
 impl TopoMap {
-    fn size(&self) -> (usize, usize) {
-        (self.map.len(), self.map[0].len())
-    }
-
-    fn trailheads(&self) -> Vec<(isize, isize)> {
-        let (my, mx) = self.size();
-        iproduct!(0..my, 0..mx)
-            .filter(|(y, x)| self.map[*y][*x] == 0)
-            .map(|(y, x)| (y as isize, x as isize))
+    fn trailheads(&self) -> Vec<Point> {
+        self.map
+            .iter()
+            .filter(|(_, level)| **level == 0)
+            .map(|(p, _)| p)
             .collect()
     }
 
-    fn in_map(&self, pos: (isize, isize)) -> bool {
-        (0..self.map.len() as isize).contains(&pos.0)
-            && (0..self.map[0].len() as isize).contains(&pos.1)
-    }
-
-    fn level(&self, pos: (isize, isize)) -> Option<u32> {
-        if self.in_map(pos) {
-            Some(self.map[pos.0 as usize][pos.1 as usize])
-        } else {
-            None
-        }
+    // The positions next to `pos` that are one level higher.
+    fn uphill(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        let level = self.map[pos];
+        self.map
+            .neighbors4(pos)
+            .filter(move |p| self.map[*p] == level + 1)
     }
 }
 
-lazy_static! {
-    static ref DIRS: Vec<(isize, isize)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-}
+pub struct Day10;
 
-pub fn day10(args: &[String]) {
-    println!("Day 10");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+impl Solution for Day10 {
+    type Input = TopoMap;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let topomap: TopoMap = contents.parse().unwrap();
-    let trailheads = topomap.trailheads();
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-    let part1: usize = trailheads
-        .iter()
-        .map(|t| solve_part1(&topomap, *t).len())
-        .sum();
-    println!("Part 1: {}", part1);
+    fn part1(topomap: &Self::Input) -> usize {
+        topomap
+            .trailheads()
+            .iter()
+            .map(|t| solve_part1(topomap, *t).len())
+            .sum()
+    }
 
-    let part2: usize = trailheads.iter().map(|t| solve_part2(&topomap, *t)).sum();
-    println!("Part 2: {}", part2);
+    fn part2(topomap: &Self::Input) -> usize {
+        topomap
+            .trailheads()
+            .iter()
+            .map(|t| solve_part2(topomap, *t))
+            .sum()
+    }
 }
 
-fn solve_part1(topomap: &TopoMap, pos: (isize, isize)) -> Vec<(isize, isize)> {
-    let level = topomap.level(pos).unwrap();
-    if level == 9 {
+fn solve_part1(topomap: &TopoMap, pos: Point) -> Vec<Point> {
+    if topomap.map[pos] == 9 {
         vec![pos]
     } else {
-        DIRS.iter()
-            .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
-            .filter(|(y, x)| topomap.level((*y, *x)) == Some(level + 1))
-            .map(|pos2| solve_part1(&topomap, pos2))
-            .flatten()
+        topomap
+            .uphill(pos)
+            .flat_map(|pos2| solve_part1(topomap, pos2))
             .unique()
             .collect()
     }
 }
 
-fn solve_part2(topomap: &TopoMap, pos: (isize, isize)) -> usize {
-    let level = topomap.level(pos).unwrap();
-    if level == 9 {
+fn solve_part2(topomap: &TopoMap, pos: Point) -> usize {
+    if topomap.map[pos] == 9 {
         return 1;
     };
-    DIRS.iter()
-        .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
-        .filter(|(y, x)| topomap.level((*y, *x)) == Some(level + 1))
-        .map(|pos2| solve_part2(&topomap, pos2))
+    topomap
+        .uphill(pos)
+        .map(|pos2| solve_part2(topomap, pos2))
         .sum()
 }
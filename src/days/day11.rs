@@ -1,37 +1,37 @@
+use crate::error::{self, Result};
+use crate::solution::Solution;
 use std::collections::HashMap;
-use std::fs;
 
-pub fn day11(args: &[String]) {
-    println!("Day 11");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.split_ascii_whitespace().map(error::number).collect()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let mut stones: Vec<usize> = contents
-        .split_ascii_whitespace()
-        .map(|v| v.parse().unwrap())
-        .collect();
-    for _ in 1..=25 {
-        stones = blink(stones);
+    fn part1(stones: &Self::Input) -> usize {
+        let mut stones = stones.clone();
+        for _ in 1..=25 {
+            stones = blink(stones);
+        }
+        stones.len()
     }
-    println!("Part 1: {}", stones.len());
 
-    let mut stones_counts: HashMap<usize, usize> = HashMap::new();
-    contents
-        .split_ascii_whitespace()
-        .map(|v| v.parse().unwrap())
-        .for_each(|v| {
-            let count = stones_counts.entry(v).or_insert(0);
+    fn part2(stones: &Self::Input) -> usize {
+        let mut stones_counts: HashMap<usize, usize> = HashMap::new();
+        stones.iter().for_each(|v| {
+            let count = stones_counts.entry(*v).or_insert(0);
             *count += 1;
         });
-    for _ in 1..=75 {
-        stones_counts = blink2(stones_counts);
+        for _ in 1..=75 {
+            stones_counts = blink2(stones_counts);
+        }
+        stones_counts.values().sum()
     }
-    println!("Part 2: {}", stones_counts.values().sum::<usize>());
 }
 
 fn next_stones(v: usize) -> Vec<usize> {
@@ -39,7 +39,7 @@ fn next_stones(v: usize) -> Vec<usize> {
         vec![1]
     } else {
         let l = ((v + 1) as f64).log10().ceil() as u32;
-        if l % 2 == 0 {
+        if l.is_multiple_of(2) {
             let s = 10_usize.pow(l / 2);
             vec![v / s, v % s]
         } else {
@@ -49,11 +49,7 @@ fn next_stones(v: usize) -> Vec<usize> {
 }
 
 fn blink(stones: Vec<usize>) -> Vec<usize> {
-    stones
-        .into_iter()
-        .map(|v| next_stones(v))
-        .flatten()
-        .collect()
+    stones.into_iter().flat_map(next_stones).collect()
 }
 
 fn blink2(stones: HashMap<usize, usize>) -> HashMap<usize, usize> {
@@ -1,36 +1,38 @@
-use itertools::iproduct;
-use std::fs;
+use crate::error::Result;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::solution::Solution;
 
-lazy_static! {
-    static ref DIRS: Vec<(isize, isize)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-}
+/// The plots of a region, and the fences around it as the side of the plot
+/// they are on and the plot.
+pub type AreaPerimeter = (Vec<Point>, Vec<(Direction, Point)>);
+
+pub struct Day12;
 
-type AreaPerimeter = (Vec<(isize, isize)>, Vec<(isize, isize, isize, isize)>);
+impl Solution for Day12 {
+    type Input = Vec<AreaPerimeter>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-pub fn day12(args: &[String]) {
-    println!("Day 12");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(all_areas(&Grid::parse(input)?))
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let map: Vec<Vec<char>> = contents.lines().map(|l| l.chars().collect()).collect();
+    fn part1(areas: &Self::Input) -> usize {
+        part1(areas)
+    }
 
-    let areas = all_areas(&map);
-    println!("Part 1: {}", part1(&areas));
-    println!("Part 2: {}", part2(&areas));
+    fn part2(areas: &Self::Input) -> usize {
+        part2(areas)
+    }
 }
 
-fn all_areas(map: &Vec<Vec<char>>) -> Vec<AreaPerimeter> {
-    let mut test_positions: Vec<_> =
-        iproduct!(0..map.len() as isize, 0..map[0].len() as isize).collect();
+fn all_areas(map: &Grid<char>) -> Vec<AreaPerimeter> {
+    let mut test_positions: Vec<_> = map.positions().collect();
 
     let mut areas = vec![];
     while !test_positions.is_empty() {
-        let (area, perimeter) = get_area_perimeter(&map, test_positions[0]);
+        let (area, perimeter) = get_area_perimeter(map, test_positions[0]);
         for p in &area {
             test_positions.remove(test_positions.iter().position(|x| *x == *p).unwrap());
         }
@@ -39,71 +41,49 @@ fn all_areas(map: &Vec<Vec<char>>) -> Vec<AreaPerimeter> {
     areas
 }
 
-fn in_map(map: &Vec<Vec<char>>, pos: (isize, isize)) -> bool {
-    (0..map.len() as isize).contains(&pos.0) && (0..map[0].len() as isize).contains(&pos.1)
-}
-
-fn get_area_perimeter(map: &Vec<Vec<char>>, pos: (isize, isize)) -> AreaPerimeter {
-    let label = map[pos.0 as usize][pos.1 as usize];
+fn get_area_perimeter(map: &Grid<char>, pos: Point) -> AreaPerimeter {
+    let label = map[pos];
     let mut test_pos = vec![pos];
     let mut positions = vec![];
     let mut perimeters = vec![];
 
     while let Some(p) = test_pos.pop() {
         positions.push(p);
-        DIRS.iter()
-            .map(|(dy, dx)| (*dy, *dx, p.0 + dy, p.1 + dx))
-            .for_each(|(dy, dx, y, x)| {
-                if !positions.contains(&(y, x)) && !test_pos.contains(&(y, x)) {
-                    if in_map(&map, (y, x)) && map[y as usize][x as usize] == label {
-                        test_pos.push((y, x));
-                    } else {
-                        perimeters.push((dy, dx, p.0, p.1));
-                    }
+        for d in Direction::ALL {
+            let next = p + d.delta();
+            if !positions.contains(&next) && !test_pos.contains(&next) {
+                if map.get(next) == Some(&label) {
+                    test_pos.push(next);
+                } else {
+                    perimeters.push((d, p));
                 }
-            })
+            }
+        }
     }
     (positions, perimeters)
 }
 
-fn part1(areas: &Vec<AreaPerimeter>) -> usize {
+fn part1(areas: &[AreaPerimeter]) -> usize {
     areas.iter().map(|(a, p)| a.len() * p.len()).sum()
 }
 
-fn part2(areas: &Vec<AreaPerimeter>) -> usize {
+fn part2(areas: &[AreaPerimeter]) -> usize {
     areas
         .iter()
         .map(|(area, perimeter)| {
             let mut sides = 0;
             let mut p = perimeter.clone();
-            while let Some((dy, dx, y, x)) = p.pop() {
+            while let Some((d, pos)) = p.pop() {
                 sides += 1;
-                // consume matching edge
-                if dy == 0 {
-                    // vertical edge
-                    for m in vec![-1, 1] {
-                        for i in 1.. {
-                            let index = p.iter().position(|(fdy, fdx, fy, fx)| {
-                                *fdy == dy && *fdx == dx && *fy == y + m * i && *fx == x
-                            });
-                            if index.is_none() {
-                                break;
-                            }
-                            p.remove(index.unwrap());
-                        }
-                    }
-                } else {
-                    // horizontal edge
-                    for m in vec![-1, 1] {
-                        for i in 1.. {
-                            let index = p.iter().position(|(fdy, fdx, fy, fx)| {
-                                *fdy == dy && *fdx == dx && *fy == y && *fx == x + m * i
-                            });
-                            if index.is_none() {
-                                break;
-                            }
-                            p.remove(index.unwrap());
-                        }
+                // consume the rest of the fences along this side
+                let along = d.turn_right().delta();
+                for m in [-1, 1] {
+                    for i in 1.. {
+                        let index = p.iter().position(|f| *f == (d, pos + along * (m * i)));
+                        match index {
+                            Some(index) => p.remove(index),
+                            None => break,
+                        };
                     }
                 }
             }
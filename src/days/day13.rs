@@ -1,10 +1,10 @@
+use crate::error::{self, Error, Result};
+use crate::parallel::prelude::*;
+use crate::solution::Solution;
 use approx::relative_eq;
 use itertools::iproduct;
-use lstsq;
 use nalgebra::{self as na, OMatrix, OVector, U2};
 use regex::Regex;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -15,15 +15,15 @@ struct Button {
 }
 
 impl FromStr for Button {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(r"Button ([A-Z]): X\+(\d+), Y\+(\d+)").unwrap();
-        let caps = re.captures(s).unwrap();
+        let caps = error::captures(&re, s)?;
         Ok(Self {
             label: caps[1].chars().next().unwrap(),
-            x: caps[2].parse().unwrap(),
-            y: caps[3].parse().unwrap(),
+            x: error::number(&caps[2])?,
+            y: error::number(&caps[3])?,
         })
     }
 }
@@ -35,34 +35,43 @@ struct Prize {
 }
 
 impl FromStr for Prize {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
-        let caps = re.captures(s).unwrap();
+        let caps = error::captures(&re, s)?;
         Ok(Self {
-            x: caps[1].parse().unwrap(),
-            y: caps[2].parse().unwrap(),
+            x: error::number(&caps[1])?,
+            y: error::number(&caps[2])?,
         })
     }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Claw {
+pub struct Claw {
     a: Button,
     b: Button,
     prize: Prize,
 }
 
 impl FromStr for Claw {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut lines = s.lines();
         Ok(Self {
-            a: lines.next().unwrap().parse().unwrap(),
-            b: lines.next().unwrap().parse().unwrap(),
-            prize: lines.next().unwrap().parse().unwrap(),
+            a: lines
+                .next()
+                .ok_or_else(|| Error::missing(s, "button A"))?
+                .parse()?,
+            b: lines
+                .next()
+                .ok_or_else(|| Error::missing(s, "button B"))?
+                .parse()?,
+            prize: lines
+                .next()
+                .ok_or_else(|| Error::missing(s, "prize"))?
+                .parse()?,
         })
     }
 }
@@ -77,7 +86,7 @@ impl Claw {
     fn part1_tokens(&self) -> Option<usize> {
         iproduct!(0..100, 0..100)
             .filter(|p| self.hit_prize(*p))
-            .map(|p| tokens(p))
+            .map(tokens)
             .min()
     }
 
@@ -114,22 +123,22 @@ pub fn tokens(presses: (usize, usize)) -> usize {
     presses.0 * 3 + presses.1
 }
 
-pub fn day13(args: &[String]) {
-    println!("Day 13");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day13;
 
-    let sections = contents.split("\n\n");
-    let claws: Vec<Claw> = sections.map(|s| s.parse().unwrap()).collect();
+impl Solution for Day13 {
+    type Input = Vec<Claw>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let part1: usize = claws.iter().filter_map(|c| c.part1_tokens()).sum();
-    println!("Part 1: {}", part1);
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.split("\n\n").map(|s| s.parse()).collect()
+    }
 
-    let part2: usize = claws.iter().filter_map(|c| c.part2_tokens()).sum();
-    println!("Part 2: {}", part2);
+    fn part1(claws: &Self::Input) -> usize {
+        claws.par_iter().filter_map(|c| c.part1_tokens()).sum()
+    }
+
+    fn part2(claws: &Self::Input) -> usize {
+        claws.par_iter().filter_map(|c| c.part2_tokens()).sum()
+    }
 }
@@ -1,110 +1,117 @@
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::solution::Solution;
+use log::debug;
 use regex::Regex;
 use std::collections::HashMap;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Robot {
-    position: (i32, i32),
-    velocity: (i32, i32),
+pub struct Robot {
+    position: Point,
+    velocity: Point,
 }
 
 impl FromStr for Robot {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(r"p=(\d+),(\d+)\sv=(\-?\d+),(\-?\d+)").unwrap();
-        let caps = re.captures(&s).unwrap();
+        let caps = error::captures(&re, s)?;
         Ok(Self {
-            position: (caps[1].parse().unwrap(), caps[2].parse().unwrap()),
-            velocity: (caps[3].parse().unwrap(), caps[4].parse().unwrap()),
+            position: Point::new(error::number(&caps[1])?, error::number(&caps[2])?),
+            velocity: Point::new(error::number(&caps[3])?, error::number(&caps[4])?),
         })
     }
 }
 
 impl Robot {
-    fn step(&mut self, size: (i32, i32)) {
-        self.position.0 = (self.position.0 + self.velocity.0 + size.0) % size.0;
-        self.position.1 = (self.position.1 + self.velocity.1 + size.1) % size.1;
+    fn step(&mut self, size: Point) {
+        let p = self.position + self.velocity;
+        self.position = Point::new(p.x.rem_euclid(size.x), p.y.rem_euclid(size.y));
     }
 }
 
-pub fn day14(args: &[String]) {
-    println!("Day 14");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day14;
 
-    let robots: Vec<Robot> = contents.lines().map(|r| r.parse().unwrap()).collect();
+impl Solution for Day14 {
+    type Input = (Vec<Robot>, Point);
+    type Part1 = usize;
+    type Part2 = Option<usize>;
 
-    let is_example = robots.len() == 12;
-    let size = if is_example { (11, 7) } else { (101, 103) };
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
 
-    println!("Part 1: {}", part1(robots.clone(), size));
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let robots: Vec<Robot> = input.lines().map(|r| r.parse()).collect::<Result<_>>()?;
+        let size = Point::new(params.get("width", 101)?, params.get("height", 103)?);
+        if size.x <= 0 || size.y <= 0 {
+            return Err(Error::Usage(format!("Invalid size: {}x{}", size.x, size.y)));
+        }
+        Ok((robots, size))
+    }
 
-    // only try part2 with the real data
-    if !is_example {
-        println!("Part 2: {}", part2(robots.clone(), size).unwrap());
+    fn part1((robots, size): &Self::Input) -> usize {
+        part1(robots.clone(), *size)
+    }
+
+    fn part2((robots, size): &Self::Input) -> Option<usize> {
+        part2(robots.clone(), *size)
     }
 }
 
-fn part1(mut robots: Vec<Robot>, size: (i32, i32)) -> usize {
+fn part1(mut robots: Vec<Robot>, size: Point) -> usize {
     for _ in 0..100 {
         for r in &mut robots {
             r.step(size);
         }
     }
     let mut quads = vec![vec![0; 2]; 2];
-    let divide = ((size.0 / 2) as i32, (size.1 / 2) as i32);
+    let divide = Point::new(size.x / 2, size.y / 2);
     for r in &mut robots {
-        if r.position.0 != divide.0 && r.position.1 != divide.1 {
-            let (qx, qy) = (if r.position.0 < divide.0 { 0 } else { 1 },
-                if r.position.1 < divide.1 { 0 } else { 1 });
+        if r.position.x != divide.x && r.position.y != divide.y {
+            let (qx, qy) = (
+                if r.position.x < divide.x { 0 } else { 1 },
+                if r.position.y < divide.y { 0 } else { 1 },
+            );
             quads[qx][qy] += 1
         }
     }
     quads.iter().flatten().product()
 }
 
-fn part2(mut robots: Vec<Robot>, size: (i32, i32)) -> Option<usize> {
+fn part2(mut robots: Vec<Robot>, size: Point) -> Option<usize> {
     for s in 1..10_000 {
         for r in &mut robots {
             r.step(size);
         }
-        // NOTE: a pattern appears at this interval
-        // if s % 101 == 68 {
-        //     println!("Step: {}", s);
-        //     print_bathroom(&robots, size);
-        // }
         if has_long_line(&robots, size) {
-            print_bathroom(&robots, size);
+            debug!("after {} seconds:\n{}", s, bathroom(&robots, size));
             return Some(s);
         }
     }
     None
 }
 
-fn get_counts(robots: &Vec<Robot>) -> HashMap<(i32, i32), i32> {
-    let mut counts: HashMap<(i32, i32), i32> = HashMap::new();
-    for r in robots.clone() {
+fn get_counts(robots: &[Robot]) -> HashMap<Point, i32> {
+    let mut counts: HashMap<Point, i32> = HashMap::new();
+    for r in robots {
         let count = counts.entry(r.position).or_insert(0);
         *count += 1;
     }
     counts
 }
 
-fn has_long_line(robots: &Vec<Robot>, size: (i32, i32)) -> bool {
-    let counts = get_counts(&robots);
+fn has_long_line(robots: &[Robot], size: Point) -> bool {
+    let counts = get_counts(robots);
     for pos in counts.keys() {
         let mut run = 0;
-        let mut x = pos.0;
+        let mut x = pos.x;
         loop {
-            if x < size.0 && counts.contains_key(&(x, pos.1)) {
+            if x < size.x && counts.contains_key(&Point::new(x, pos.y)) {
                 run += 1;
                 if run > 30 {
                     return true;
@@ -118,18 +125,10 @@ fn has_long_line(robots: &Vec<Robot>, size: (i32, i32)) -> bool {
     false
 }
 
-fn print_bathroom(robots: &Vec<Robot>, size: (i32, i32)) {
-    let counts = get_counts(&robots);
-    let mut output = Vec::new();
-    for y in 0..size.1 {
-        for x in 0..size.0 {
-            if counts.contains_key(&(x, y)) {
-                output.push("X".to_string());
-            } else {
-                output.push(".".to_string());
-            }
-        }
-        output.push("\n".to_string());
+fn bathroom(robots: &[Robot], size: Point) -> Grid<char> {
+    let mut bathroom = Grid::new(size.x as usize, size.y as usize, '.');
+    for r in robots {
+        bathroom[r.position] = 'X';
     }
-    println!("{}", output.join(""));
+    bathroom
 }
@@ -1,50 +1,34 @@
-use itertools::iproduct;
+use crate::error::{self, Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::solution::Solution;
+use log::{debug, trace};
 use std::collections::HashSet;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Warehouse {
-    map: Vec<Vec<char>>,
-    robot: (usize, usize),
+pub struct Warehouse {
+    map: Grid<char>,
+    robot: Point,
 }
 
 impl FromStr for Warehouse {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut m: Vec<Vec<char>> = s.lines().map(|l| l.chars().collect()).collect();
-        let r = iproduct!(0..m.len(), 0..m[0].len())
-            .find(|(y, x)| m[*y][*x] == '@')
-            .unwrap();
-        m[r.0][r.1] = '.';
+    fn from_str(s: &str) -> Result<Self> {
+        error::chars(s, "#.O@")?;
+        let mut m = Grid::parse(s)?;
+        let r = m.find(&'@').ok_or_else(|| Error::missing(s, "robot `@`"))?;
+        m[r] = '.';
         Ok(Self { map: m, robot: r })
     }
 }
 
 impl Warehouse {
-    fn step(&mut self, direction: char) {
-        let d: (isize, isize) = match direction {
-            '^' => (-1, 0),
-            'v' => (1, 0),
-            '<' => (0, -1),
-            '>' => (0, 1),
-            _ => unreachable!("Unknown move"),
-        };
-        let p = (
-            (self.robot.0 as isize + d.0) as usize,
-            (self.robot.1 as isize + d.1) as usize,
-        );
-        self.robot = match self.map[p.0][p.1] {
+    fn step(&mut self, direction: Direction) {
+        let d = direction.delta();
+        let p = self.robot + d;
+        self.robot = match self.map[p] {
             '.' => p,
             '#' => self.robot,
             'O' => {
@@ -52,11 +36,8 @@ impl Warehouse {
                 let mut nextp = None;
                 let mut p2 = p;
                 loop {
-                    p2 = (
-                        (p2.0 as isize + d.0) as usize,
-                        (p2.1 as isize + d.1) as usize,
-                    );
-                    match self.map[p2.0][p2.1] {
+                    p2 += d;
+                    match self.map[p2] {
                         '.' => {
                             nextp = Some(p2);
                             break;
@@ -68,8 +49,8 @@ impl Warehouse {
                     };
                 }
                 if let Some(np) = nextp {
-                    self.map[p.0][p.1] = '.';
-                    self.map[np.0][np.1] = 'O';
+                    self.map[p] = '.';
+                    self.map[np] = 'O';
                     p
                 } else {
                     self.robot
@@ -80,66 +61,54 @@ impl Warehouse {
     }
 
     fn gps_sum(&self) -> usize {
-        iproduct!(0..self.map.len(), 0..self.map[0].len())
-            .filter(|(y, x)| self.map[*y][*x] == 'O')
-            .map(|(y, x)| y * 100 + x)
-            .sum()
+        gps_sum(&self.map, 'O')
     }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Warehouse2 {
-    map: Vec<Vec<char>>,
-    robot: (usize, usize),
+pub struct Warehouse2 {
+    map: Grid<char>,
+    robot: Point,
 }
 
 impl FromStr for Warehouse2 {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut m: Vec<Vec<char>> = s
-            .lines()
-            .map(|l| {
-                l.chars()
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        error::chars(s, "#.O@")?;
+        let wide: String = Grid::parse(s)?
+            .rows()
+            .map(|row| {
+                let row: String = row
+                    .iter()
                     .map(|c| match c {
-                        '#' => vec!['#', '#'],
-                        'O' => vec!['[', ']'],
-                        '.' => vec!['.', '.'],
-                        '@' => vec!['@', '.'],
+                        '#' => "##",
+                        'O' => "[]",
+                        '.' => "..",
+                        '@' => "@.",
                         _ => unreachable!(),
                     })
-                    .flatten()
-                    .collect::<Vec<char>>()
+                    .collect();
+                row + "\n"
             })
             .collect();
-        let r = iproduct!(0..m.len(), 0..m[0].len())
-            .find(|(y, x)| m[*y][*x] == '@')
-            .unwrap();
-        m[r.0][r.1] = '.';
+        let mut m = Grid::parse(&wide)?;
+        let r = m.find(&'@').ok_or_else(|| Error::missing(s, "robot `@`"))?;
+        m[r] = '.';
         Ok(Self { map: m, robot: r })
     }
 }
 
 impl Warehouse2 {
-    fn step(&mut self, direction: char) {
-        let (dir, d): (Direction, (isize, isize)) = match direction {
-            '^' => (Direction::Up, (-1, 0)),
-            'v' => (Direction::Down, (1, 0)),
-            '<' => (Direction::Left, (0, -1)),
-            '>' => (Direction::Right, (0, 1)),
-            _ => unreachable!("Unknown move"),
-        };
-        let p = (
-            (self.robot.0 as isize + d.0) as usize,
-            (self.robot.1 as isize + d.1) as usize,
-        );
-        let c = self.map[p.0][p.1];
+    fn step(&mut self, direction: Direction) {
+        let p = self.robot + direction.delta();
+        let c = self.map[p];
         self.robot = match c {
             '.' => p,
             '#' => self.robot,
             '[' | ']' => {
-                let block = if c == '[' { p } else { (p.0, p.1 - 1) };
-                if self.move_blocks(block, dir) {
+                let block = if c == '[' { p } else { p + LEFT };
+                if self.move_blocks(block, direction) {
                     p
                 } else {
                     self.robot
@@ -149,9 +118,9 @@ impl Warehouse2 {
         }
     }
 
-    fn move_blocks(&mut self, block: (usize, usize), dir: Direction) -> bool {
+    fn move_blocks(&mut self, block: Point, dir: Direction) -> bool {
         let testblocks = self.connected_blocks(block, dir);
-        // println!("connected: {:?}", testblocks);
+        trace!("connected: {:?}", testblocks);
         let mut warehouse_temp = self.map.clone();
         let allmoved = testblocks.iter().rev().all(|l| {
             l.iter()
@@ -163,40 +132,23 @@ impl Warehouse2 {
         allmoved
     }
 
-    fn connected_blocks(
-        &mut self,
-        block: (usize, usize),
-        dir: Direction,
-    ) -> Vec<HashSet<(usize, usize)>> {
+    fn connected_blocks(&mut self, block: Point, dir: Direction) -> Vec<HashSet<Point>> {
         let mut connected = vec![];
         let mut testblocks = vec![(0, block)];
         while let Some((l, b)) = testblocks.pop() {
             if l + 1 > connected.len() {
                 connected.push(HashSet::new());
             }
-            connected[l].insert(b.clone());
-            match dir {
-                Direction::Down | Direction::Up => {
-                    let ny = if dir == Direction::Up {
-                        b.0 - 1
-                    } else {
-                        b.0 + 1
-                    };
-                    for nx in b.1 - 1..=b.1 + 1 {
-                        if self.map[ny][nx] == '[' {
-                            testblocks.push((l + 1, (ny, nx)));
-                        }
-                    }
-                }
-                Direction::Left | Direction::Right => {
-                    let nx = if dir == Direction::Left {
-                        b.1 - 2
-                    } else {
-                        b.1 + 2
-                    };
-                    if self.map[b.0][nx] == '[' {
-                        testblocks.push((l + 1, (b.0, nx)));
-                    }
+            connected[l].insert(b);
+            let next = if dir.is_horizontal() {
+                vec![b + dir.delta() * 2]
+            } else {
+                let n = b + dir.delta();
+                vec![n + LEFT, n, n + RIGHT]
+            };
+            for n in next {
+                if self.map[n] == '[' {
+                    testblocks.push((l + 1, n));
                 }
             }
         }
@@ -204,112 +156,82 @@ impl Warehouse2 {
     }
 
     // block is the left edge position
-    fn move_block(map: &mut Vec<Vec<char>>, block: (usize, usize), dir: Direction) -> bool {
+    fn move_block(map: &mut Grid<char>, block: Point, dir: Direction) -> bool {
         if Self::can_move(map, block, dir) {
-            match dir {
-                Direction::Up => {
-                    map[block.0 - 1][block.1] = '[';
-                    map[block.0 - 1][block.1 + 1] = ']';
-                    map[block.0][block.1] = '.';
-                    map[block.0][block.1 + 1] = '.';
-                }
-                Direction::Down => {
-                    map[block.0 + 1][block.1] = '[';
-                    map[block.0 + 1][block.1 + 1] = ']';
-                    map[block.0][block.1] = '.';
-                    map[block.0][block.1 + 1] = '.';
-                }
-                Direction::Left => {
-                    map[block.0][block.1 - 1] = '[';
-                    map[block.0][block.1] = ']';
-                    map[block.0][block.1 + 1] = '.';
-                }
-                Direction::Right => {
-                    map[block.0][block.1] = '.';
-                    map[block.0][block.1 + 1] = '[';
-                    map[block.0][block.1 + 2] = ']';
-                }
-            }
+            map[block] = '.';
+            map[block + RIGHT] = '.';
+            let moved = block + dir.delta();
+            map[moved] = '[';
+            map[moved + RIGHT] = ']';
             return true;
         }
         false
     }
 
-    fn can_move(map: &Vec<Vec<char>>, block: (usize, usize), dir: Direction) -> bool {
+    fn can_move(map: &Grid<char>, block: Point, dir: Direction) -> bool {
         match dir {
             Direction::Down | Direction::Up => {
-                let ny = if dir == Direction::Up {
-                    block.0 - 1
-                } else {
-                    block.0 + 1
-                };
-                map[ny][block.1] == '.' && map[ny][block.1 + 1] == '.'
-            }
-            Direction::Left | Direction::Right => {
-                let nx = if dir == Direction::Left {
-                    block.1 - 1
-                } else {
-                    block.1 + 2
-                };
-                map[block.0][nx] == '.'
+                let n = block + dir.delta();
+                map[n] == '.' && map[n + RIGHT] == '.'
             }
+            Direction::Left => map[block + LEFT] == '.',
+            Direction::Right => map[block + RIGHT * 2] == '.',
         }
     }
 
     fn gps_sum(&self) -> usize {
-        iproduct!(0..self.map.len(), 0..self.map[0].len())
-            .filter(|(y, x)| self.map[*y][*x] == '[')
-            .map(|(y, x)| y * 100 + x)
-            .sum()
+        gps_sum(&self.map, '[')
     }
 }
 
-pub fn day15(args: &[String]) {
-    println!("Day 15");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+const LEFT: Point = Point::new(-1, 0);
+const RIGHT: Point = Point::new(1, 0);
 
-    let mut sections = contents.split("\n\n");
+// The sum of the GPS coordinates of every box drawn with `c`.
+fn gps_sum(map: &Grid<char>, c: char) -> usize {
+    map.iter()
+        .filter(|(_, v)| **v == c)
+        .map(|(p, _)| (p.y * 100 + p.x) as usize)
+        .sum()
+}
 
-    let mut warehouse: Warehouse = sections.next().unwrap().parse().unwrap();
-    let moves: Vec<char> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
-        .flatten()
-        .collect();
+pub struct Day15;
 
-    // println!("Start: {:?}", warehouse.robot);
-    moves.iter().for_each(|m| warehouse.step(*m));
-    println!("Part 1: {}", warehouse.gps_sum());
+impl Solution for Day15 {
+    type Input = (Warehouse, Warehouse2, Vec<Direction>);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    sections = contents.split("\n\n");
-    let mut warehouse2: Warehouse2 = sections.next().unwrap().parse().unwrap();
-    // println!("Start2: {:?}", warehouse2.robot);
-    moves.iter().for_each(|m| {
-        warehouse2.step(*m);
-    });
-    print_warehouse(&warehouse2.map, warehouse2.robot);
-    println!("Part 2: {}", warehouse2.gps_sum());
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (map, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::missing(input, "robot moves"))?;
+        error::chars(moves, "^v<>")?;
+        let moves = moves.chars().filter_map(Direction::from_arrow).collect();
+        Ok((map.parse()?, map.parse()?, moves))
+    }
 
-fn print_warehouse(map: &Vec<Vec<char>>, robot: (usize, usize)) {
-    let mut output = Vec::new();
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if (y, x) == robot {
-                output.push("@".to_string());
-            } else {
-                output.push(map[y][x].to_string());
-            }
-        }
-        output.push("\n".to_string());
+    fn part1((warehouse, _, moves): &Self::Input) -> usize {
+        let mut warehouse = warehouse.clone();
+        moves.iter().for_each(|m| warehouse.step(*m));
+        warehouse.gps_sum()
+    }
+
+    fn part2((_, warehouse2, moves): &Self::Input) -> usize {
+        let mut warehouse2 = warehouse2.clone();
+        moves.iter().for_each(|m| {
+            warehouse2.step(*m);
+        });
+        debug!(
+            "final warehouse:\n{}",
+            render(&warehouse2.map, warehouse2.robot)
+        );
+        warehouse2.gps_sum()
     }
-    println!("{}", output.join(""));
+}
+
+fn render(map: &Grid<char>, robot: Point) -> Grid<char> {
+    let mut map = map.clone();
+    map[robot] = '@';
+    map
 }
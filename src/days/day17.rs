@@ -1,9 +1,14 @@
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use itertools::Itertools;
+use log::{debug, trace, warn};
 use regex::Regex;
-use std::{fs, num::ParseIntError, str::FromStr, usize};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Computer {
+pub struct Computer {
     registers: Vec<usize>,
     ip: usize,
     program: Vec<usize>,
@@ -11,24 +16,37 @@ struct Computer {
 }
 
 impl FromStr for Computer {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(
             r"Register A: (\d+)\nRegister B: (\d+)\nRegister C: (\d+)\n\nProgram: ([\d\,]+)",
         )
         .unwrap();
-        let caps = re.captures(s).unwrap();
-        let a = caps.get(1).unwrap().as_str().parse::<usize>().unwrap();
-        let b = caps.get(2).unwrap().as_str().parse::<usize>().unwrap();
-        let c = caps.get(3).unwrap().as_str().parse::<usize>().unwrap();
-        let p = caps
-            .get(4)
-            .unwrap()
-            .as_str()
-            .split(",")
-            .map(|c| c.parse::<usize>().unwrap())
-            .collect();
+        let caps = error::captures(&re, s)?;
+        let a = error::number(&caps[1])?;
+        let b = error::number(&caps[2])?;
+        let c = error::number(&caps[3])?;
+        let words: Vec<&str> = caps[4].split(',').collect();
+        let p = words
+            .iter()
+            .map(|c| match error::number(c)? {
+                v @ 0..=7 => Ok(v),
+                _ => Err(Error::parse(c, format!("`{}` is not a 3-bit number", c))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if p.len() % 2 != 0 {
+            return Err(Error::parse(
+                &caps[4],
+                "program has an opcode without an operand",
+            ));
+        }
+        if let Some(i) = (0..p.len())
+            .step_by(2)
+            .find(|i| Opcode::from_code(p[*i]).takes_combo() && p[i + 1] == 7)
+        {
+            return Err(Error::parse(words[i + 1], "combo operand 7 is reserved"));
+        }
 
         Ok(Self {
             registers: vec![a, b, c],
@@ -39,55 +57,89 @@ impl FromStr for Computer {
     }
 }
 
+// The puzzle input format, which `from_str` reads back.
+impl fmt::Display for Computer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, value) in REGISTERS.iter().zip(&self.registers) {
+            writeln!(f, "Register {}: {}", name, value)?;
+        }
+        writeln!(f, "\nProgram: {}", self.program.iter().join(","))
+    }
+}
+
 impl Computer {
+    pub fn program(&self) -> &[usize] {
+        &self.program
+    }
+
+    /// The address of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Everything the program has output so far.
+    pub fn output(&self) -> &[usize] {
+        &self.output
+    }
+
+    /// The values of registers A, B and C.
+    pub fn registers(&self) -> [usize; 3] {
+        [self.registers[0], self.registers[1], self.registers[2]]
+    }
+
     fn step(&mut self) -> bool {
-        if self.ip >= self.program.len() {
+        // a jump can leave the instruction pointer on the last number,
+        // which has no operand after it
+        if self.ip + 1 >= self.program.len() {
             return false;
         }
-        let opcode = self.program[self.ip];
+        let opcode = Opcode::from_code(self.program[self.ip]);
         let operand = self.program[self.ip + 1];
         self.ip += 2;
-        // println!("{} {}", opcode, operand);
-        // println!("{:?}", self);
+        trace!("{:?} {} {:?}", opcode, operand, self);
         match opcode {
-            0 => {
-                self.registers[0] /= 2_usize.pow(self.combo_operand(operand) as u32);
+            Opcode::Adv => {
+                self.registers[0] = self.divide(operand);
             }
-            1 => {
+            Opcode::Bxl => {
                 self.registers[1] ^= operand;
             }
-            2 => {
+            Opcode::Bst => {
                 self.registers[1] = self.combo_operand(operand) % 8;
             }
-            3 => {
+            Opcode::Jnz => {
                 if self.registers[0] != 0 {
                     self.ip = operand;
                 }
             }
-            4 => {
+            Opcode::Bxc => {
                 self.registers[1] ^= self.registers[2];
             }
-            5 => {
+            Opcode::Out => {
                 self.output.push(self.combo_operand(operand) % 8);
             }
-            6 => {
-                self.registers[1] =
-                    self.registers[0] / 2_usize.pow(self.combo_operand(operand) as u32);
+            Opcode::Bdv => {
+                self.registers[1] = self.divide(operand);
             }
-            7 => {
-                self.registers[2] =
-                    self.registers[0] / 2_usize.pow(self.combo_operand(operand) as u32);
-            }
-            _ => {
-                unreachable!("Unknown opcode: {}", opcode);
+            Opcode::Cdv => {
+                self.registers[2] = self.divide(operand);
             }
         };
         true
     }
 
+    // A divided by 2 to the power of the combo operand, which is 0 once the
+    // power is more than A can hold, like `Expr::Shr`.
+    fn divide(&self, operand: usize) -> usize {
+        u32::try_from(self.combo_operand(operand))
+            .ok()
+            .and_then(|shift| self.registers[0].checked_shr(shift))
+            .unwrap_or(0)
+    }
+
     fn combo_operand(&self, value: usize) -> usize {
         match value {
-            0 | 1 | 2 | 3 => value,
+            0..=3 => value,
             4 => self.registers[0],
             5 => self.registers[1],
             6 => self.registers[2],
@@ -96,59 +148,809 @@ impl Computer {
     }
 }
 
-pub fn day17(args: &[String]) {
-    println!("Day 17");
-    if args.len() != 1 {
-        println!("Missing input file.");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let mut cpu: Computer = contents.parse().unwrap();
-
-    loop {
-        if !cpu.step() {
-            println!("Part 1: {}", cpu.output.iter().join(","));
-            break;
-        }
-    }
-
-    let cpu: Computer = contents.parse().unwrap();
-    // run until the bottom 24 bits of register A are known, then
-    // use that as a the base to start counting from.
-    let mut i = 0;
-    let mut best = 0;
-    let mut lowerbits = 0;
-    let mut lowerbitcount = 0;
-    loop {
-        let a = i << lowerbitcount | lowerbits;
-        let mut cpu2 = cpu.clone();
-        cpu2.registers[0] = a;
-        loop {
-            if !cpu2.step() {
-                break;
-            }
-            if cpu2.output != cpu2.program[0..cpu2.output.len()] {
-                break;
-            } else {
-                if cpu2.output.len() > best {
-                    best = cpu2.output.len();
-                    // println!("{:#020x} {} -> {:?}", a, a, cpu2.output);
-                    if (a >> 24) > 0 {
-                        lowerbits = a & 0xFFFFFF;
-                        lowerbitcount = 24;
-                        i = 0;
-                        break;
+/// The computer's eight instructions, in opcode order.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    /// The instruction for a 3-bit opcode.
+    pub fn from_code(code: usize) -> Opcode {
+        Self::ALL[code]
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, which can name a register,
+    /// rather than a literal.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+/// Render a program as one instruction per line, with combo operands
+/// resolved to a register or literal and jump targets labelled, e.g.
+///
+/// ```text
+/// L0:
+///      0  adv 1
+///      2  out A
+///      4  jnz L0
+/// ```
+pub fn disassemble(program: &[usize]) -> String {
+    let targets: Vec<usize> = program
+        .chunks(2)
+        .filter(|i| Opcode::from_code(i[0]) == Opcode::Jnz)
+        .map(|i| i[1])
+        // a jump into the middle of an instruction keeps its number
+        .filter(|t| t.is_multiple_of(2) && *t <= program.len())
+        .sorted()
+        .dedup()
+        .collect();
+    let label = |address: usize| format!("L{}", address);
+
+    let mut lines = vec![];
+    for (i, instruction) in program.chunks(2).enumerate() {
+        let address = i * 2;
+        if targets.contains(&address) {
+            lines.push(format!("{}:", label(address)));
+        }
+        let (opcode, operand) = (Opcode::from_code(instruction[0]), instruction[1]);
+        let operand = match opcode {
+            Opcode::Jnz if targets.contains(&operand) => format!(" {}", label(operand)),
+            // the operand is ignored, but kept so the listing assembles
+            // back to the same program
+            Opcode::Bxc if operand == 0 => String::new(),
+            _ if opcode.takes_combo() => match operand {
+                0..=3 => format!(" {}", operand),
+                4..=6 => format!(" {}", REGISTERS[operand - 4]),
+                _ => " 7  ; reserved".to_string(),
+            },
+            _ => format!(" {}", operand),
+        };
+        lines.push(format!("{:>6}  {}{}", address, opcode.mnemonic(), operand));
+    }
+    if targets.contains(&program.len()) {
+        lines.push(format!("{}:", label(program.len())));
+    }
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+/// The names of the registers, in order.
+pub const REGISTERS: [char; 3] = ['A', 'B', 'C'];
+
+/// Build a computer from mnemonic source, the inverse of `disassemble`:
+///
+/// ```text
+/// .A 2024       ; registers not set this way start at 0
+/// loop:
+///     adv 3
+///     out A     ; combo operands can name a register
+///     jnz loop  ; or jnz 0
+/// ```
+///
+/// A line can start with its address, as in a listing, which must match
+/// where the instruction ends up. `bxc` ignores its operand, so it may be
+/// left out.
+pub fn assemble(source: &str) -> Result<Computer> {
+    let mut registers = vec![0; 3];
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut instructions = vec![];
+    for line in source.lines() {
+        let mut line = line.split(';').next().unwrap().trim();
+        while let Some((label, rest)) = line.split_once(':') {
+            let label = label.trim();
+            let valid = label.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !valid {
+                return Err(Error::parse(label, format!("invalid label `{}`", label)));
+            }
+            if labels.insert(label, instructions.len() * 2).is_some() {
+                return Err(Error::parse(
+                    label,
+                    format!("label `{}` is already defined", label),
+                ));
+            }
+            line = rest.trim();
+        }
+
+        let mut words = line.split_whitespace();
+        let Some(mut word) = words.next() else {
+            continue;
+        };
+        if let Some(name) = word.strip_prefix('.') {
+            let register = register(name)
+                .ok_or_else(|| Error::parse(word, format!("unknown register `{}`", name)))?;
+            let value = words.next().ok_or_else(|| Error::missing(word, "value"))?;
+            registers[register] = error::number(value)?;
+        } else {
+            if word.starts_with(|c: char| c.is_ascii_digit()) {
+                let address: usize = error::number(word)?;
+                if address != instructions.len() * 2 {
+                    return Err(Error::parse(
+                        word,
+                        format!("expected address {}", instructions.len() * 2),
+                    ));
+                }
+                word = words
+                    .next()
+                    .ok_or_else(|| Error::missing(word, "instruction"))?;
+            }
+            let opcode = Opcode::ALL
+                .into_iter()
+                .find(|o| o.mnemonic() == word)
+                .ok_or_else(|| Error::parse(word, format!("unknown instruction `{}`", word)))?;
+            instructions.push((opcode, word, words.next()));
+        }
+        if let Some(extra) = words.next() {
+            return Err(Error::parse(extra, format!("unexpected `{}`", extra)));
+        }
+    }
+
+    let mut program = vec![];
+    for (opcode, mnemonic, operand) in instructions {
+        let operand = match (opcode, operand) {
+            (Opcode::Bxc, None) => 0,
+            (_, None) => return Err(Error::missing(mnemonic, "operand")),
+            (Opcode::Jnz, Some(label)) if labels.contains_key(label) => match labels[label] {
+                // the operand is a literal, so only the first few
+                // instructions can be jumped to
+                address @ 0..=7 => address,
+                address => {
+                    return Err(Error::parse(
+                        label,
+                        format!("`{}` is at {}, too far to jump to", label, address),
+                    ))
+                }
+            },
+            (Opcode::Jnz, Some(label)) if label.starts_with(|c: char| !c.is_ascii_digit()) => {
+                return Err(Error::parse(label, format!("undefined label `{}`", label)))
+            }
+            (_, Some(operand)) => match register(operand) {
+                Some(r) if opcode.takes_combo() => r + 4,
+                _ => match operand.parse() {
+                    Ok(7) if opcode.takes_combo() => {
+                        return Err(Error::parse(operand, "combo operand 7 is reserved"))
+                    }
+                    Ok(v @ 0..=7) => v,
+                    _ => {
+                        return Err(Error::parse(
+                            operand,
+                            format!("`{}` is not a 3-bit operand", operand),
+                        ))
                     }
+                },
+            },
+        };
+        program.extend([opcode as usize, operand]);
+    }
+    Ok(Computer {
+        registers,
+        ip: 0,
+        program,
+        output: vec![],
+    })
+}
+
+fn register(name: &str) -> Option<usize> {
+    REGISTERS
+        .iter()
+        .position(|r| name.len() == 1 && name.starts_with(*r))
+}
+
+/// How many earlier states the debugger keeps for stepping back.
+const HISTORY: usize = 100_000;
+
+/// How many instructions `Debugger::run` runs before giving up, by default.
+/// It is the same as the history, so that all of them can be stepped back
+/// through.
+pub const DEFAULT_LIMIT: usize = HISTORY;
+
+/// Why the debugger stopped running the program.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Stop {
+    /// It ran as many steps as it was asked to.
+    Stepped,
+    /// The instruction pointer reached a breakpoint.
+    Breakpoint(usize),
+    /// A watched register changed.
+    Watch {
+        register: char,
+        from: usize,
+        to: usize,
+    },
+    /// The instruction pointer left the program.
+    Halted,
+    /// There is no earlier state to step back to.
+    Start,
+    /// It ran this many instructions without anything stopping it.
+    Limit(usize),
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stop::Stepped => Ok(()),
+            Stop::Breakpoint(address) => write!(f, "breakpoint at {}", address),
+            Stop::Watch { register, from, to } => {
+                write!(f, "{} changed from {} to {}", register, from, to)
+            }
+            Stop::Halted => write!(f, "halted"),
+            Stop::Start => write!(f, "at the earliest recorded state"),
+            Stop::Limit(steps) => write!(f, "still running after {} steps", steps),
+        }
+    }
+}
+
+/// Runs a computer one instruction at a time, stopping at breakpoints and
+/// when watched registers change. Every state is recorded, so it can also
+/// step backwards.
+pub struct Debugger {
+    computer: Computer,
+    history: VecDeque<Computer>,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<usize>,
+    limit: usize,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Self {
+            computer,
+            history: VecDeque::new(),
+            breakpoints: BTreeSet::new(),
+            watches: BTreeSet::new(),
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn set_breakpoint(&mut self, address: usize, on: bool) {
+        if on {
+            self.breakpoints.insert(address);
+        } else {
+            self.breakpoints.remove(&address);
+        }
+    }
+
+    /// Watch register `index` (0 for A) for changes.
+    pub fn set_watch(&mut self, index: usize, on: bool) {
+        if on {
+            self.watches.insert(index);
+        } else {
+            self.watches.remove(&index);
+        }
+    }
+
+    /// Run up to `count` instructions.
+    pub fn step(&mut self, count: usize) -> Stop {
+        (0..count)
+            .find_map(|_| self.step_once())
+            .unwrap_or(Stop::Stepped)
+    }
+
+    /// Stop `run` after this many instructions, so that a program that
+    /// never halts can be looked at.
+    pub fn set_limit(&mut self, steps: usize) {
+        self.limit = steps;
+    }
+
+    /// Run until something stops the program, or the limit is reached.
+    pub fn run(&mut self) -> Stop {
+        (0..self.limit)
+            .find_map(|_| self.step_once())
+            .unwrap_or(Stop::Limit(self.limit))
+    }
+
+    /// Undo up to `count` instructions.
+    pub fn back(&mut self, count: usize) -> Stop {
+        for _ in 0..count {
+            match self.history.pop_back() {
+                Some(computer) => self.computer = computer,
+                None => return Stop::Start,
+            }
+        }
+        Stop::Stepped
+    }
+
+    fn step_once(&mut self) -> Option<Stop> {
+        let before = self.computer.clone();
+        if !self.computer.step() {
+            return Some(Stop::Halted);
+        }
+        let changed = self
+            .watches
+            .iter()
+            .find(|r| before.registers[**r] != self.computer.registers[**r])
+            .map(|r| Stop::Watch {
+                register: REGISTERS[*r],
+                from: before.registers[*r],
+                to: self.computer.registers[*r],
+            });
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(before);
+        changed.or_else(|| {
+            self.breakpoints
+                .contains(&self.computer.ip)
+                .then_some(Stop::Breakpoint(self.computer.ip))
+        })
+    }
+
+    /// Carry out one line of a debugging session and describe the result.
+    pub fn command(&mut self, line: &str) -> Result<String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let count = |words: &[&str]| match words {
+            [] => Ok(1),
+            [n] => n
+                .parse()
+                .map_err(|_| Error::Usage(format!("Invalid count: {}", n))),
+            _ => Err(Error::Usage(format!("Unexpected argument: {}", words[1]))),
+        };
+        let address = |words: &[&str]| match words {
+            [n] => n
+                .parse()
+                .map_err(|_| Error::Usage(format!("Invalid address: {}", n))),
+            _ => Err(Error::Usage("Expected an address".to_string())),
+        };
+        let register = |words: &[&str]| match words {
+            [r] => register(r).ok_or_else(|| Error::Usage(format!("Unknown register: {}", r))),
+            _ => Err(Error::Usage("Expected a register".to_string())),
+        };
+
+        let Some((command, args)) = words.split_first() else {
+            return Ok(String::new());
+        };
+        let stop = match *command {
+            "s" | "step" => self.step(count(args)?),
+            "r" | "back" => self.back(count(args)?),
+            "c" | "continue" => self.run(),
+            "b" | "break" | "d" | "delete" => {
+                let address = address(args)?;
+                let on = matches!(*command, "b" | "break");
+                self.set_breakpoint(address, on);
+                let verb = if on { "set" } else { "deleted" };
+                return Ok(format!("breakpoint at {} {}", address, verb));
+            }
+            "w" | "watch" | "u" | "unwatch" => {
+                let index = register(args)?;
+                let on = matches!(*command, "w" | "watch");
+                self.set_watch(index, on);
+                let verb = if on { "watching" } else { "not watching" };
+                return Ok(format!("{} {}", verb, REGISTERS[index]));
+            }
+            "limit" if args.is_empty() => {
+                return Ok(format!("continue stops after {} steps", self.limit))
+            }
+            "limit" => {
+                let steps = count(args)?;
+                self.set_limit(steps);
+                return Ok(format!("continue stops after {} steps", steps));
+            }
+            "o" | "output" => return Ok(self.computer.output.iter().join(",")),
+            "l" | "list" => return Ok(self.listing()),
+            "p" | "print" => Stop::Stepped,
+            "h" | "help" => return Ok(HELP.trim_end().to_string()),
+            _ => return Err(Error::Usage(format!("Unknown command: {}", command))),
+        };
+        Ok(match stop {
+            Stop::Stepped => self.status(),
+            stop => format!("{}\n{}", stop, self.status()),
+        })
+    }
+
+    // The registers and the next instruction.
+    fn status(&self) -> String {
+        let registers = REGISTERS
+            .iter()
+            .zip(&self.computer.registers)
+            .map(|(name, value)| format!("{} {}", name, value))
+            .join("  ");
+        let next = self
+            .listing_lines()
+            .into_iter()
+            .find(|(address, _)| *address == Some(self.computer.ip))
+            .and_then(|(_, line)| Some(line.trim().split_once(' ')?.1.trim().to_string()))
+            .unwrap_or_else(|| "(halted)".to_string());
+        format!("{}  ip {}: {}", registers, self.computer.ip, next)
+    }
+
+    // The program's listing, with the next instruction and the breakpoints
+    // marked.
+    fn listing(&self) -> String {
+        self.listing_lines()
+            .into_iter()
+            .map(|(address, line)| {
+                let next = if address == Some(self.computer.ip) {
+                    '>'
+                } else {
+                    ' '
+                };
+                let on = address.is_some_and(|a| self.breakpoints.contains(&a));
+                format!("{}{} {}", next, if on { '*' } else { ' ' }, line)
+            })
+            .join("\n")
+    }
+
+    // Each line of the listing, with the address of its instruction.
+    fn listing_lines(&self) -> Vec<(Option<usize>, String)> {
+        disassemble(&self.computer.program)
+            .lines()
+            .map(|line| {
+                let address = line.split_whitespace().next().and_then(|a| a.parse().ok());
+                (address, line.to_string())
+            })
+            .collect()
+    }
+}
+
+const HELP: &str = "\
+s, step [N]       run N instructions (1)
+r, back [N]       undo N instructions (1)
+c, continue       run until a breakpoint, a watched change or the end
+limit N           stop continuing after N instructions (100000)
+b, break ADDR     stop when the instruction pointer reaches ADDR
+d, delete ADDR    remove a breakpoint
+w, watch REG      stop when register A, B or C changes
+u, unwatch REG    stop watching a register
+p, print          show the registers and the next instruction
+o, output         show the output so far
+l, list           show the program
+h, help           show this help
+q, quit           stop debugging
+";
+
+/// Whether `program` is a single loop like `adv 3 ... out ... jnz 0`, which
+/// shifts three bits out of A and outputs one value each time around. The
+/// value may only depend on A: if it reads B or C before setting them, it
+/// depends on what the last time around left in them.
+pub fn is_digit_loop(program: &[usize]) -> bool {
+    let Some(iteration) = decompile(program) else {
+        return false;
+    };
+    let shift = Expr::Shr(Box::new(Expr::Register(0)), Box::new(Expr::Literal(3)));
+    iteration.loops
+        && iteration.registers[0] == shift
+        && iteration.outputs.len() == 1
+        && !iteration.outputs[0].uses(1)
+        && !iteration.outputs[0].uses(2)
+}
+
+/// The smallest value of register A that makes a digit loop (see
+/// `is_digit_loop`) output its own program, if there is one.
+///
+/// Each time around, the loop outputs a value that depends on the low
+/// octal digits of A and then drops the lowest one. So the last output
+/// only depends on the highest digit, the one before it on the highest
+/// two, and so on, and A can be built up one digit at a time from the end
+/// of the program, backtracking when no digit produces the next value.
+/// Trying the digits in increasing order finds the smallest A first.
+pub fn quine(computer: &Computer) -> Option<usize> {
+    if !is_digit_loop(&computer.program) {
+        return None;
+    }
+    find_digits(computer, computer.program.len() - 1, 0)
+}
+
+// The smallest A that starts with the octal digits `high` and outputs the
+// program from `from` onwards.
+fn find_digits(computer: &Computer, from: usize, high: usize) -> Option<usize> {
+    (0..8).find_map(|digit| {
+        let a = high << 3 | digit;
+        if output_for(computer, a) != computer.program[from..] {
+            return None;
+        }
+        debug!(
+            "A = {:o} outputs the last {}",
+            a,
+            computer.program.len() - from
+        );
+        match from {
+            0 => Some(a),
+            _ => find_digits(computer, from - 1, a),
+        }
+    })
+}
+
+// The output with register A set to `a`, or as much of it as is needed to
+// tell whether it is the program.
+fn output_for(computer: &Computer, a: usize) -> Vec<usize> {
+    let mut computer = computer.clone();
+    computer.registers[0] = a;
+    while computer.output.len() <= computer.program.len() && computer.step() {}
+    computer.output
+}
+
+/// A value computed by a program, in terms of the registers at the start of
+/// a loop iteration.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Expr {
+    /// The register with this index (0 for A).
+    Register(usize),
+    Literal(usize),
+    Xor(Box<Expr>, Box<Expr>),
+    /// The first value divided by 2 to the power of the second.
+    Shr(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+}
+
+impl Expr {
+    /// The value of the expression for the given register values.
+    pub fn eval(&self, registers: [usize; 3]) -> usize {
+        match self {
+            Expr::Register(r) => registers[*r],
+            Expr::Literal(v) => *v,
+            Expr::Xor(l, r) => l.eval(registers) ^ r.eval(registers),
+            Expr::Shr(l, r) => match r.eval(registers) {
+                shift @ 0..64 => l.eval(registers) >> shift,
+                _ => 0,
+            },
+            Expr::Mod8(e) => e.eval(registers) % 8,
+        }
+    }
+
+    // These build expressions in a simpler form where they can, so that
+    // e.g. two `bxl`s in a row become a single xor.
+
+    fn xor(l: Expr, r: Expr) -> Expr {
+        match (l, r) {
+            (Expr::Literal(l), Expr::Literal(r)) => Expr::Literal(l ^ r),
+            (Expr::Literal(0), e) | (e, Expr::Literal(0)) => e,
+            (Expr::Literal(v), e) | (e, Expr::Literal(v)) => match e {
+                Expr::Xor(l, r) if matches!(*r, Expr::Literal(_)) => {
+                    Expr::xor(*l, Expr::xor(*r, Expr::Literal(v)))
                 }
+                e => Expr::Xor(Box::new(e), Box::new(Expr::Literal(v))),
+            },
+            (l, r) => Expr::Xor(Box::new(l), Box::new(r)),
+        }
+    }
+
+    fn shr(l: Expr, r: Expr) -> Expr {
+        match (l, r) {
+            (l, Expr::Literal(0)) => l,
+            (Expr::Literal(l), Expr::Literal(r)) if r < 64 => Expr::Literal(l >> r),
+            (Expr::Shr(l, inner), Expr::Literal(r)) => match *inner {
+                Expr::Literal(m) => Expr::shr(*l, Expr::Literal(m + r)),
+                inner => Expr::Shr(
+                    Box::new(Expr::Shr(l, Box::new(inner))),
+                    Box::new(Expr::Literal(r)),
+                ),
+            },
+            (l, r) => Expr::Shr(Box::new(l), Box::new(r)),
+        }
+    }
+
+    fn mod8(e: Expr) -> Expr {
+        match e {
+            Expr::Literal(v) => Expr::Literal(v % 8),
+            e if e.below8() => e,
+            e => Expr::Mod8(Box::new(e)),
+        }
+    }
+
+    fn below8(&self) -> bool {
+        match self {
+            Expr::Literal(v) => *v < 8,
+            Expr::Mod8(_) => true,
+            Expr::Xor(l, r) => l.below8() && r.below8(),
+            _ => false,
+        }
+    }
+
+    fn uses(&self, register: usize) -> bool {
+        match self {
+            Expr::Register(r) => *r == register,
+            Expr::Literal(_) => false,
+            Expr::Xor(l, r) | Expr::Shr(l, r) => l.uses(register) || r.uses(register),
+            Expr::Mod8(e) => e.uses(register),
+        }
+    }
+
+    // The expression as an operand of another, in parentheses unless it is
+    // a single value.
+    fn operand(&self) -> String {
+        match self {
+            Expr::Register(_) | Expr::Literal(_) => self.to_string(),
+            _ => format!("({})", self),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Register(r) => write!(f, "{}", REGISTERS[*r]),
+            Expr::Literal(v) => write!(f, "{}", v),
+            // xor is associative, so a chain of them needs no parentheses
+            Expr::Xor(l, r) => {
+                let term = |e: &Expr| match e {
+                    Expr::Xor(..) => e.to_string(),
+                    _ => e.operand(),
+                };
+                write!(f, "{} ^ {}", term(l), term(r))
             }
+            Expr::Shr(l, r) => write!(f, "{} >> {}", l.operand(), r.operand()),
+            Expr::Mod8(e) => write!(f, "{} % 8", e.operand()),
+        }
+    }
+}
+
+/// What one run through a program does: the values it outputs, and the
+/// registers it leaves for the next time around if it loops.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Iteration {
+    pub outputs: Vec<Expr>,
+    pub registers: [Expr; 3],
+    /// Whether the program ends with `jnz 0`.
+    pub loops: bool,
+}
+
+/// Run a program symbolically, with every value an expression of the
+/// registers at the start. This needs a program without jumps, or a single
+/// loop with `jnz 0` as its last and only jump, and without combo operand 7.
+pub fn decompile(program: &[usize]) -> Option<Iteration> {
+    let mut instructions: Vec<(Opcode, usize)> = program
+        .chunks(2)
+        .map(|i| (Opcode::from_code(i[0]), i[1]))
+        .collect();
+    let loops = instructions.last() == Some(&(Opcode::Jnz, 0));
+    if loops {
+        instructions.pop();
+    }
+
+    let mut registers = [0, 1, 2].map(Expr::Register);
+    let mut outputs = vec![];
+    for (opcode, operand) in instructions {
+        let combo = match operand {
+            0..=3 => Expr::Literal(operand),
+            4..=6 => registers[operand - 4].clone(),
+            _ if opcode.takes_combo() => return None,
+            _ => Expr::Literal(operand),
+        };
+        let divided = || Expr::shr(registers[0].clone(), combo.clone());
+        match opcode {
+            Opcode::Adv => registers[0] = divided(),
+            Opcode::Bxl => registers[1] = Expr::xor(registers[1].clone(), Expr::Literal(operand)),
+            Opcode::Bst => registers[1] = Expr::mod8(combo),
+            Opcode::Jnz => return None,
+            Opcode::Bxc => registers[1] = Expr::xor(registers[1].clone(), registers[2].clone()),
+            Opcode::Out => outputs.push(Expr::mod8(combo)),
+            Opcode::Bdv => registers[1] = divided(),
+            Opcode::Cdv => registers[2] = divided(),
+        }
+    }
+    Some(Iteration {
+        outputs,
+        registers,
+        loops,
+    })
+}
+
+/// The iteration as Rust-like code, e.g.
+///
+/// ```text
+/// loop {
+///     out(((A % 8) ^ 4 ^ (A >> ((A % 8) ^ 1))) % 8);
+///     A >>= 3;
+///     if A == 0 {
+///         break;
+///     }
+/// }
+/// ```
+impl fmt::Display for Iteration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = if self.loops { "    " } else { "" };
+        if self.loops {
+            writeln!(f, "loop {{")?;
+        }
+        for output in &self.outputs {
+            writeln!(f, "{}out({});", indent, output)?;
+        }
+        // a register only needs updating if the next time around reads
+        // the value this one left in it
+        let carried: Vec<usize> = (0..3)
+            .filter(|r| self.registers[*r] != Expr::Register(*r))
+            .filter(|r| {
+                !self.loops
+                    || *r == 0
+                    || self
+                        .outputs
+                        .iter()
+                        .chain(&self.registers)
+                        .any(|e| e.uses(*r))
+            })
+            .collect();
+        match &carried[..] {
+            [] => {}
+            [r] => match &self.registers[*r] {
+                Expr::Shr(l, shift) if **l == Expr::Register(*r) => {
+                    writeln!(f, "{}{} >>= {};", indent, REGISTERS[*r], shift.operand())?
+                }
+                e => writeln!(f, "{}{} = {};", indent, REGISTERS[*r], e)?,
+            },
+            // every new value is computed from the old ones
+            _ => writeln!(
+                f,
+                "{}({}) = ({});",
+                indent,
+                carried.iter().map(|r| REGISTERS[*r]).join(", "),
+                carried.iter().map(|r| &self.registers[*r]).join(", ")
+            )?,
+        }
+        if self.loops {
+            writeln!(f, "    if A == 0 {{")?;
+            writeln!(f, "        break;")?;
+            writeln!(f, "    }}")?;
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Computer;
+    type Part1 = String;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(cpu: &Self::Input) -> String {
+        let mut cpu = cpu.clone();
+        while cpu.step() {}
+        cpu.output.iter().join(",")
+    }
+
+    fn part2(cpu: &Self::Input) -> Option<usize> {
+        if !is_digit_loop(&cpu.program) {
+            warn!("The program isn't a loop like `adv 3 ... out ... jnz 0`");
+            return None;
         }
-        if cpu2.output == cpu2.program {
-            println!("Part 2: {}", a);
-            break;
+        let a = quine(cpu);
+        if a.is_none() {
+            warn!("No value of A makes the program output itself");
         }
-        i += 1;
+        a
     }
 }
@@ -1,86 +1,93 @@
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::search;
+use crate::solution::Solution;
 use itertools::Itertools;
-use std::collections::BinaryHeap;
-use std::fs;
+use log::trace;
 
-pub fn day18(args: &[String]) {
-    println!("Day 18");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day18;
 
-    let positions: Vec<(usize, usize)> = contents
-        .lines()
-        .map(|l| {
-            l.split(",")
-                .map(|v| v.parse().unwrap())
-                .collect_tuple()
-                .unwrap()
-        })
-        .collect();
+impl Solution for Day18 {
+    type Input = (Vec<Point>, (usize, usize), usize);
+    type Part1 = Option<usize>;
+    type Part2 = Option<String>;
 
-    let mut map: Vec<Vec<char>> = if positions.len() == 25 {
-        vec![vec!['.'; 7]; 7]
-    } else {
-        vec![vec!['.'; 71]; 71]
-    };
-    let bytes = if positions.len() == 25 { 12 } else { 1024 };
-    for p in &positions[0..bytes] {
-        map[p.1][p.0] = '#';
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
     }
-    // print_map(&map);
-    let part1 = shortest_path(&map);
-    println!("Part 1: {}", part1.unwrap());
 
-    for p in &positions[bytes..] {
-        map[p.1][p.0] = '#';
-        if shortest_path(&map).is_none() {
-            println!("Part 2: {},{}", p.0, p.1);
-            break;
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let positions: Vec<Point> = input
+            .lines()
+            .map(|l| {
+                l.split(',')
+                    .map(error::number)
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .collect_tuple()
+                    .map(|(x, y)| Point::new(x, y))
+                    .ok_or_else(|| Error::parse(l, "expected an `X,Y` position"))
+            })
+            .collect::<Result<_>>()?;
+        let size = (params.get("width", 71)?, params.get("height", 71)?);
+        let bytes = params.get("bytes", 1024)?;
+        if size.0 == 0 || size.1 == 0 {
+            return Err(Error::Usage(format!("Invalid size: {}x{}", size.0, size.1)));
+        }
+        if let Some((i, _)) = positions.iter().find_position(|p| {
+            !(0..size.0 as isize).contains(&p.x) || !(0..size.1 as isize).contains(&p.y)
+        }) {
+            let l = input.lines().nth(i).unwrap();
+            return Err(Error::parse(
+                l,
+                format!("position is outside the {}x{} memory space", size.0, size.1),
+            ));
+        }
+        if positions.len() < bytes {
+            return Err(Error::missing(input, &format!("{} falling bytes", bytes)));
         }
+        Ok((positions, size, bytes))
     }
-}
 
-lazy_static! {
-    static ref ADJ: Vec<(isize, isize)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-}
+    fn part1((positions, size, bytes): &Self::Input) -> Option<usize> {
+        let map = fallen_map(&positions[0..*bytes], *size);
+        trace!("fallen bytes:\n{}", map);
+        shortest_path(&map)
+    }
 
-pub fn adjacent(pos: (isize, isize)) -> Vec<(isize, isize)> {
-    ADJ.iter()
-        .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
-        .collect()
+    fn part2((positions, size, bytes): &Self::Input) -> Option<String> {
+        // binary search for the number of fallen bytes that first blocks
+        // the exit
+        let counts: Vec<usize> = (*bytes..=positions.len()).collect();
+        let i = counts
+            .partition_point(|n| shortest_path(&fallen_map(&positions[..*n], *size)).is_some());
+        let p = positions[counts.get(i)?.checked_sub(1)?];
+        Some(format!("{},{}", p.x, p.y))
+    }
 }
 
-pub fn map_value(map: &Vec<Vec<char>>, p: (isize, isize)) -> char {
-    if (0..map.len() as isize).contains(&p.0) && (0..map[0].len() as isize).contains(&p.1) {
-        return map[p.0 as usize][p.1 as usize];
+fn fallen_map(positions: &[Point], size: (usize, usize)) -> Grid<char> {
+    let mut map = Grid::new(size.0, size.1, '.');
+    for p in positions {
+        map[*p] = '#';
     }
-    '#'
+    map
 }
 
-fn shortest_path(map: &Vec<Vec<char>>) -> Option<i32> {
-    let mut lowest = vec![vec![i32::MAX; map[0].len()]; map.len()];
-    let mut positions = BinaryHeap::new();
-    positions.push((0, (0, 0)));
-    let dest = (map.len() as isize - 1, map[0].len() as isize - 1);
-
-    while let Some((cur_score, cur_pos)) = positions.pop() {
-        if cur_pos == dest {
-            return Some(-cur_score);  // at end
-        }
-        for next_pos in adjacent(cur_pos) {
-            if map_value(&map, next_pos) == '#' {
-                continue;
-            }
-            let next_score = -cur_score + 1;
-            if next_score < lowest[next_pos.0 as usize][next_pos.1 as usize] {
-                positions.push((-next_score, next_pos));
-                lowest[next_pos.0 as usize][next_pos.1 as usize] = next_score;
-            }
-        }
-    }
-    None
+fn shortest_path(map: &Grid<char>) -> Option<usize> {
+    let dest = Point::new(map.width() as isize - 1, map.height() as isize - 1);
+    let neighbors = |p: &Point| {
+        map.neighbors4(*p)
+            .filter(|n| map[*n] != '#')
+            .map(|n| (n, 1))
+    };
+    let search = search::astar(
+        Point::ZERO,
+        neighbors,
+        |p| p.manhattan(dest) as usize,
+        |p| *p == dest,
+    );
+    search.cost(&dest)
 }
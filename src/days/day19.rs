@@ -1,48 +1,44 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use log::trace;
 use std::collections::{BinaryHeap, HashMap};
-use std::fs;
 
-pub fn day19(args: &[String]) {
-    println!("Day 19");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day19;
 
-    let mut sections = contents.split("\n\n");
+impl Solution for Day19 {
+    type Input = (Vec<String>, Vec<String>);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let patterns: Vec<_> = sections
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|s| s.to_string())
-        .collect();
-    let designs: Vec<_> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|s| s.to_string())
-        .collect();
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (patterns, designs) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::missing(input, "designs"))?;
 
-    // println!("{:?}", patterns);
-    // println!("{:?}", designs);
+        let patterns: Vec<_> = patterns.split(", ").map(|s| s.to_string()).collect();
+        let designs: Vec<_> = designs.lines().map(|s| s.to_string()).collect();
 
-    let part2: usize = designs
-        .iter()
-        .filter(|d| towel_patterns1(&patterns, d.to_string()))
-        .count();
-    println!("Part 1: {:?}", part2);
+        trace!("patterns: {:?}", patterns);
+        trace!("designs: {:?}", designs);
+        Ok((patterns, designs))
+    }
 
-    let part1: usize = designs
-        .iter()
-        .map(|d| towel_patterns2(&mut HashMap::new(), &patterns, d.to_string()))
-        .sum();
-    println!("Part 2: {:?}", part1);
+    fn part1((patterns, designs): &Self::Input) -> usize {
+        designs
+            .iter()
+            .filter(|d| towel_patterns1(patterns, d.to_string()))
+            .count()
+    }
+
+    fn part2((patterns, designs): &Self::Input) -> usize {
+        designs
+            .iter()
+            .map(|d| towel_patterns2(&mut HashMap::new(), patterns, d.to_string()))
+            .sum()
+    }
 }
 
-fn towel_patterns1(patterns: &Vec<String>, design: String) -> bool {
+fn towel_patterns1(patterns: &[String], design: String) -> bool {
     let mut designs = BinaryHeap::new();
     designs.push((-(design.len() as i32), design.clone()));
 
@@ -51,7 +47,7 @@ fn towel_patterns1(patterns: &Vec<String>, design: String) -> bool {
         if solutions.contains_key(&d) {
             continue;
         }
-        // println!("{} {}", l, d);
+        trace!("{} {}", l, d);
         if l == 0 {
             return true;
         }
@@ -70,21 +66,21 @@ fn towel_patterns1(patterns: &Vec<String>, design: String) -> bool {
 
 fn towel_patterns2(
     solutions: &mut HashMap<String, usize>,
-    patterns: &Vec<String>,
+    patterns: &[String],
     design: String,
 ) -> usize {
-    // println!("{}", design);
-    if solutions.contains_key(&design) {
-        return *solutions.get(&design).unwrap();
+    trace!("{}", design);
+    if let Some(sum) = solutions.get(&design) {
+        return *sum;
     }
-    if design.len() == 0 {
+    if design.is_empty() {
         return 1;
     }
     let mut sum = 0;
     for p in patterns {
         if design.starts_with(p) {
             let next_d = design[p.len()..].to_string();
-            sum += towel_patterns2(solutions, &patterns, next_d);
+            sum += towel_patterns2(solutions, patterns, next_d);
         }
     }
     solutions.insert(design, sum);
@@ -1,47 +1,45 @@
-use std::fs;
+use crate::error::{self, Result};
+use crate::solution::Solution;
 
-pub fn day2(args: &[String]) {
-    println!("Day 2");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|l| l.split_ascii_whitespace().map(error::number).collect())
+            .collect()
+    }
+
+    fn part1(reports: &Self::Input) -> usize {
+        reports.iter().map(|r| is_safe(r)).filter(|&v| v).count()
+    }
+
+    fn part2(reports: &Self::Input) -> usize {
+        reports
+            .iter()
+            .map(|r| is_safe_without_one(r))
+            .filter(|&v| v)
+            .count()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let reports: Vec<_> = contents
-        .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|i| i.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect();
-
-    let part1 = reports.iter().map(|r| is_safe(r)).filter(|&v| v).count();
-    println!("Part 1: {}", part1);
-
-    let part2 = reports
-        .iter()
-        .map(|r| is_safe_without_one(r))
-        .filter(|&v| v)
-        .count();
-    println!("Part 2: {}", part2);
 }
 
-pub fn deltas(report: &Vec<i32>) -> Vec<i32> {
+pub fn deltas(report: &[i32]) -> Vec<i32> {
     report.windows(2).map(|v| v[1] - v[0]).collect::<Vec<_>>()
 }
 
-pub fn is_safe(report: &Vec<i32>) -> bool {
+pub fn is_safe(report: &[i32]) -> bool {
     let deltas = deltas(report);
-    deltas.iter().all(|&v| (v > 0 && v < 4)) || deltas.iter().all(|&v| (v > -4 && v < 0))
+    deltas.iter().all(|&v| v > 0 && v < 4) || deltas.iter().all(|&v| v > -4 && v < 0)
 }
 
-pub fn is_safe_without_one(report: &Vec<i32>) -> bool {
+pub fn is_safe_without_one(report: &[i32]) -> bool {
     for i in 0..report.len() {
-        let mut report2 = report.clone();
+        let mut report2 = report.to_vec();
         report2.remove(i);
         if is_safe(&report2) {
             return true;
@@ -1,98 +1,62 @@
-use itertools::iproduct;
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::search;
+use crate::solution::Solution;
 use itertools::Itertools;
-use rusttype::{point, Point};
-use std::collections::{BinaryHeap, HashMap};
-use std::{fs, usize, vec};
+use std::collections::HashMap;
 
-pub fn day20(args: &[String]) {
-    println!("Day 20");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let map: Vec<Vec<_>> = contents.lines().map(|l| l.chars().collect()).collect();
-    let path = shortest_path(&map, char_position(&map, 'S'), char_position(&map, 'E')).unwrap();
-
-    let cheats = find_cheats(&path, 2);
-    let saves = if map.len() == 15 { 1 } else { 100 };
-    let part1: usize = cheats.iter().filter(|s| **s >= saves).count();
-    println!("Part 1: {}", part1);
+pub struct Day20;
 
-    let cheats = find_cheats(&path, 20);
-    let saves = if map.len() == 15 { 50 } else { 100 };
+impl Solution for Day20 {
+    /// The map and the least time a cheat must save to be counted in each
+    /// part.
+    type Input = (Grid<char>, [i32; 2]);
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
 
-    let part2: usize = cheats.iter().filter(|s| **s >= saves).count();
-    println!("Part 2: {}", part2);
-}
-
-lazy_static! {
-    static ref ADJ: Vec<(isize, isize)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-}
-
-pub fn adjacent(pos: (isize, isize)) -> Vec<(isize, isize)> {
-    ADJ.iter()
-        .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
-        .collect()
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
 
-pub fn map_value(map: &Vec<Vec<char>>, p: (isize, isize)) -> char {
-    if (0..map.len() as isize).contains(&p.0) && (0..map[0].len() as isize).contains(&p.1) {
-        return map[p.0 as usize][p.1 as usize];
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input> {
+        let min_save = params.get("min-save", 100)?;
+        let min_save = [min_save, params.get("min-save-part2", min_save)?];
+        error::chars(input, "#.SE")?;
+        for c in ["S", "E"] {
+            if !input.contains(c) {
+                return Err(Error::missing(input, &format!("`{}` position", c)));
+            }
+        }
+        Ok((Grid::parse(input)?, min_save))
     }
-    '#' // out of bounds
-}
 
-pub fn char_position(map: &Vec<Vec<char>>, c: char) -> (isize, isize) {
-    let v = iproduct!(0..map.len(), 0..map[0].len())
-        .find(|(y, x)| map[*y][*x] == c)
-        .unwrap();
-    (v.0 as isize, v.1 as isize)
-}
+    fn part1((map, min_save): &Self::Input) -> Option<usize> {
+        let path = race_path(map)?;
+        let cheats = find_cheats(&path, 2);
+        Some(cheats.iter().filter(|s| **s >= min_save[0]).count())
+    }
 
-pub fn manhatten(a: Point<isize>, b: Point<isize>) -> isize {
-    let d = b - a;
-    d.x.abs() + d.y.abs()
+    fn part2((map, min_save): &Self::Input) -> Option<usize> {
+        let path = race_path(map)?;
+        let cheats = find_cheats(&path, 20);
+        Some(cheats.iter().filter(|s| **s >= min_save[1]).count())
+    }
 }
 
-fn shortest_path(
-    map: &Vec<Vec<char>>,
-    start_pos: (isize, isize),
-    end_pos: (isize, isize),
-) -> Option<Vec<(isize, isize)>> {
-    let mut positions = BinaryHeap::new();
-    positions.push((-1, vec![start_pos]));
-
-    while let Some((_, path)) = positions.pop() {
-        let p = path.last().unwrap();
-        if *p == end_pos {
-            return Some(path);
-        }
-        let c = map_value(&map, *p);
-        if c == '#' {
-            continue;
-        }
-        for next_pos in adjacent(*p) {
-            if path.contains(&next_pos) {
-                continue;
-            }
-            let mut next_path = path.clone();
-            next_path.push(next_pos);
-            positions.push((-(next_path.len() as i32), next_path.clone()));
-        }
-    }
-    None
+// None when the end can't be reached from the start.
+fn race_path(map: &Grid<char>) -> Option<Vec<Point>> {
+    let (start, end) = (map.find(&'S').unwrap(), map.find(&'E').unwrap());
+    let search = search::bfs(start, |p| map.neighbors4(*p).filter(|n| map[*n] != '#'));
+    search.path(&end)
 }
 
-fn find_cheats(path: &Vec<(isize, isize)>, cheat_distance: i32) -> Vec<i32> {
-    let known_psecs: HashMap<Point<isize>, i32> = path
-        .clone()
-        .into_iter()
+fn find_cheats(path: &[Point], cheat_distance: i32) -> Vec<i32> {
+    let known_psecs: HashMap<Point, i32> = path
+        .iter()
         .enumerate()
-        .map(|(i, (y, x))| (point(x, y), i as i32))
+        .map(|(i, p)| (*p, i as i32))
         .collect();
 
     known_psecs
@@ -100,7 +64,7 @@ fn find_cheats(path: &Vec<(isize, isize)>, cheat_distance: i32) -> Vec<i32> {
         .combinations(2)
         .map(|p| {
             (
-                manhatten(*p[0], *p[1]) as i32,
+                p[0].manhattan(*p[1]) as i32,
                 (known_psecs.get(p[0]).unwrap() - known_psecs.get(p[1]).unwrap()).abs(),
             )
         })
@@ -0,0 +1,124 @@
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        error::chars(input, "0123456789A")?;
+        input
+            .lines()
+            .map(|code| match code.strip_suffix('A') {
+                Some(digits) if !digits.contains('A') => Ok(code.to_string()),
+                _ => Err(Error::parse(code, "expected digits followed by `A`")),
+            })
+            .collect()
+    }
+
+    fn part1(codes: &Self::Input) -> usize {
+        complexity(codes, 2)
+    }
+
+    fn part2(codes: &Self::Input) -> usize {
+        complexity(codes, 25)
+    }
+}
+
+lazy_static! {
+    static ref NUMERIC: Grid<char> = Grid::parse("789\n456\n123\n 0A\n").unwrap();
+    static ref DIRECTIONAL: Grid<char> = Grid::parse(" ^A\n<v>\n").unwrap();
+}
+
+/// The sum over `codes` of the length of the shortest sequence that types
+/// the code through `robots` directional keypads, times the code's number.
+pub fn complexity(codes: &[String], robots: usize) -> usize {
+    let mut costs = HashMap::new();
+    codes
+        .iter()
+        .map(|code| {
+            let number: usize = code.trim_end_matches('A').parse().unwrap_or(0);
+            sequence_length(&mut costs, code, robots) * number
+        })
+        .sum()
+}
+
+/// The length of the shortest sequence of buttons to press on your own
+/// keypad to type `code` on the numeric keypad, with `robots` robots using
+/// directional keypads in between. `costs` caches presses on the
+/// directional keypads between calls.
+pub fn sequence_length(
+    costs: &mut HashMap<(char, char, usize), usize>,
+    code: &str,
+    robots: usize,
+) -> usize {
+    presses(code)
+        .map(|(from, to)| {
+            moves(&NUMERIC, from, to)
+                .iter()
+                .map(|m| typing_cost(costs, m, robots))
+                .min()
+                .unwrap()
+        })
+        .sum()
+}
+
+// The number of presses on your own keypad needed to type `sequence` on a
+// directional keypad that is `robots` robots away from you.
+fn typing_cost(
+    costs: &mut HashMap<(char, char, usize), usize>,
+    sequence: &str,
+    robots: usize,
+) -> usize {
+    if robots == 0 {
+        return sequence.len();
+    }
+    presses(sequence)
+        .map(|(from, to)| {
+            if let Some(cost) = costs.get(&(from, to, robots)) {
+                return *cost;
+            }
+            let cost = moves(&DIRECTIONAL, from, to)
+                .iter()
+                .map(|m| typing_cost(costs, m, robots - 1))
+                .min()
+                .unwrap();
+            costs.insert((from, to, robots), cost);
+            cost
+        })
+        .sum()
+}
+
+// Each pair of buttons the arm moves between to type `sequence`, starting
+// from `A`.
+fn presses(sequence: &str) -> impl Iterator<Item = (char, char)> + '_ {
+    "A".chars().chain(sequence.chars()).zip(sequence.chars())
+}
+
+// The directions that move the arm from `from` to `to` on `keypad` and then
+// press the button. Only all horizontal moves followed by all vertical ones
+// (or the other way round) can be shortest once there are robots above,
+// and either can be ruled out by passing over the gap.
+fn moves(keypad: &Grid<char>, from: char, to: char) -> Vec<String> {
+    let (from, to) = (keypad.find(&from).unwrap(), keypad.find(&to).unwrap());
+    let gap = keypad.find(&' ').unwrap();
+    let delta = to - from;
+    let horizontal = if delta.x < 0 { "<" } else { ">" }.repeat(delta.x.unsigned_abs());
+    let vertical = if delta.y < 0 { "^" } else { "v" }.repeat(delta.y.unsigned_abs());
+
+    let mut moves = vec![];
+    if Point::new(to.x, from.y) != gap {
+        moves.push(format!("{}{}A", horizontal, vertical));
+    }
+    if Point::new(from.x, to.y) != gap {
+        moves.push(format!("{}{}A", vertical, horizontal));
+    }
+    moves.dedup();
+    moves
+}
@@ -1,20 +1,22 @@
-use std::{fs, num::ParseIntError, str::FromStr};
+use std::str::FromStr;
 
+use crate::error::{self, Error, Result};
+use crate::parallel::prelude::*;
+use crate::solution::Solution;
 use itertools::{iproduct, Itertools};
-use tqdm::tqdm;
 
 #[derive(Clone, Eq, PartialEq, Debug, Copy)]
-struct Secret {
+pub struct Secret {
     number: usize,
     limit: usize,
 }
 
 impl FromStr for Secret {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         Ok(Self {
-            number: s.parse().unwrap(),
+            number: error::number(s)?,
             limit: 2000,
         })
     }
@@ -45,52 +47,45 @@ impl Iterator for SecretPriceChanges {
 
     fn next(&mut self) -> Option<Self::Item> {
         let last = self.secret.number;
-        let n = self.secret.next();
-        if n.is_none() {
-            return None;
-        }
-        Some((n.unwrap() % 10) as isize - (last % 10) as isize)
+        let n = self.secret.next()?;
+        Some((n % 10) as isize - (last % 10) as isize)
     }
 }
 
-pub fn day22(args: &[String]) {
-    println!("Day 22");
-    if args.len() != 1 {
-        println!("Missing input file.");
-        return;
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Secret>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(|l| l.parse()).collect()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let part1: usize = contents
-        .lines()
-        .map(|l| l.parse::<Secret>().unwrap())
-        .map(|s| s.skip(2000 - 1).next().unwrap())
-        .sum();
-    println!("Part 1: {}", part1);
+    fn part1(secrets: &Self::Input) -> usize {
+        secrets
+            .iter()
+            .map(|&(mut s)| s.nth(2000 - 1).unwrap())
+            .sum()
+    }
 
-    // TODO: Rework to find a faster approarch because the following brute-force is
-    // very slow (but does get the answer in around 30 minutes).
-    let secrets: Vec<Secret> = contents.lines().map(|l| l.parse().unwrap()).collect();
-    let mut best = 0;
-    for c in tqdm(iproduct!(-9..=9, -9..=9, -9..=9, -9..=9)) {
-        let b: usize = secrets.iter().filter_map(|s| get_price(*s, c)).sum();
-        if b > best {
-            best = b;
-        }
+    fn part2(secrets: &Self::Input) -> usize {
+        // TODO: Rework to find a faster approarch because the following brute-force is
+        // very slow (but does get the answer in around 30 minutes).
+        let changes: Vec<_> = iproduct!(-9..=9, -9..=9, -9..=9, -9..=9).collect();
+        changes
+            .par_iter()
+            .map(|c| secrets.iter().filter_map(|s| get_price(*s, *c)).sum())
+            .max()
+            .unwrap_or(0)
     }
-    println!("Part 2: {}", best);
 }
 
 fn get_price(secret: Secret, changes: (isize, isize, isize, isize)) -> Option<usize> {
-    let s = SecretPriceChanges { secret: secret };
-    let p = s
+    let mut s = SecretPriceChanges { secret };
+    let (p, _) = s
         .tuple_windows::<(_, _, _, _)>()
-        .find_position(|t| *t == changes);
-    if p.is_some() {
-        Some(s.secret.skip(p.unwrap().0 + 3).next().unwrap() % 10)
-    } else {
-        None
-    }
+        .find_position(|t| *t == changes)?;
+    Some(s.secret.nth(p + 3).unwrap() % 10)
 }
@@ -1,76 +1,97 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 use itertools::Itertools;
+use log::debug;
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use tqdm::tqdm;
 
-pub fn day23(args: &[String]) {
-    println!("Day 23");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Network {
+    connections: Vec<(String, String)>,
+    nodes: HashSet<String>,
+    nodes_connections: HashMap<String, HashSet<String>>,
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Network;
+    type Part1 = usize;
+    type Part2 = Option<String>;
 
-    let connections: Vec<(String, String)> = contents
-        .lines()
-        .map(|l| l.split("-").map(|s| s.to_string()).collect_tuple().unwrap())
-        .collect();
-    let nodes: HashSet<_> = connections
-        .clone()
-        .into_iter()
-        .map(|(a, b)| vec![a.to_string(), b.to_string()])
-        .flatten()
-        .collect();
-    // println!("{}: {:?}", nodes.len(), nodes);
+    fn parse(input: &str) -> Result<Self::Input> {
+        let connections: Vec<(String, String)> = input
+            .lines()
+            .map(|l| {
+                l.split('-')
+                    .map(|s| s.to_string())
+                    .collect_tuple()
+                    .ok_or_else(|| Error::parse(l, "expected an `a-b` connection"))
+            })
+            .collect::<Result<_>>()?;
+        let nodes: HashSet<_> = connections
+            .clone()
+            .into_iter()
+            .flat_map(|(a, b)| vec![a.to_string(), b.to_string()])
+            .collect();
+        debug!("{} nodes: {:?}", nodes.len(), nodes);
 
-    let mut nodes_connections = HashMap::new();
-    nodes.clone().into_iter().for_each(|n| {
-        nodes_connections.insert(n.clone(), node_connections(&connections, n));
-    });
+        let mut nodes_connections = HashMap::new();
+        nodes.clone().into_iter().for_each(|n| {
+            nodes_connections.insert(n.clone(), node_connections(&connections, n));
+        });
+        Ok(Network {
+            connections,
+            nodes,
+            nodes_connections,
+        })
+    }
 
-    let part1 = tqdm(nodes.clone().into_iter().combinations(3))
-        .filter(|n| starts_with_t(n))
-        .filter(|n| are_connected(n, &connections))
-        .count();
-    println!("Part 1: {:?}", part1);
+    fn part1(network: &Self::Input) -> usize {
+        network
+            .nodes
+            .clone()
+            .into_iter()
+            .combinations(3)
+            .filter(|n| starts_with_t(n))
+            .filter(|n| are_connected(n, &network.connections))
+            .count()
+    }
 
-    let best = nodes
-        .clone()
-        .into_iter()
-        .map(|n| node_interestions(&nodes_connections, n.clone()))
-        .max()
-        .unwrap();
-    let mut best_nodes: Vec<_> = nodes
-        .clone()
-        .into_iter()
-        .filter(|n| best == node_interestions(&nodes_connections, n.clone()))
-        .collect();
-    best_nodes.sort();
-    println!("Part 2: {}", best_nodes.join(","));
+    fn part2(network: &Self::Input) -> Option<String> {
+        let (nodes, nodes_connections) = (&network.nodes, &network.nodes_connections);
+        let best = nodes
+            .clone()
+            .into_iter()
+            .map(|n| node_interestions(nodes_connections, n.clone()))
+            .max()?;
+        let mut best_nodes: Vec<_> = nodes
+            .clone()
+            .into_iter()
+            .filter(|n| best == node_interestions(nodes_connections, n.clone()))
+            .collect();
+        best_nodes.sort();
+        Some(best_nodes.join(","))
+    }
 }
 
-fn are_connected(nodes: &Vec<String>, connections: &Vec<(String, String)>) -> bool {
+fn are_connected(nodes: &[String], connections: &[(String, String)]) -> bool {
     nodes.iter().combinations(2).all(|n| {
         connections.contains(&(n[0].clone(), n[1].clone()))
             || connections.contains(&(n[1].clone(), n[0].clone()))
     })
 }
 
-fn starts_with_t(nodes: &Vec<String>) -> bool {
-    nodes.iter().any(|n| n.starts_with("t"))
+fn starts_with_t(nodes: &[String]) -> bool {
+    nodes.iter().any(|n| n.starts_with('t'))
 }
 
-fn node_connections(connections: &Vec<(String, String)>, node: String) -> HashSet<String> {
+fn node_connections(connections: &[(String, String)], node: String) -> HashSet<String> {
     connections
-        .clone()
-        .into_iter()
+        .iter()
         .filter_map(|(a, b)| {
-            if a == node {
-                Some(b)
-            } else if b == node {
-                Some(a)
+            if *a == node {
+                Some(b.clone())
+            } else if *b == node {
+                Some(a.clone())
             } else {
                 None
             }
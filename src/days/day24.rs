@@ -1,8 +1,9 @@
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use itertools::Itertools;
+use log::{debug, trace};
 use regex::Regex;
 use std::collections::HashMap;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 lazy_static! {
@@ -20,7 +21,7 @@ static ref SWAPS: HashMap<String, String> = HashMap::from([
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Gate {
+pub struct Gate {
     ina: String,
     inb: String,
     op: String,
@@ -28,120 +29,108 @@ struct Gate {
 }
 
 impl FromStr for Gate {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let re = Regex::new(r"(\w+) (AND|OR|XOR) (\w+) -> (\w+)").unwrap();
-        let caps = re.captures(s).unwrap();
+        let caps = error::captures(&re, s)?;
         let ina = caps[1].to_string();
         let op = caps[2].to_string();
         let inb = caps[3].to_string();
         let out = caps[4].to_string();
 
-        Ok(Self {
-            ina: ina,
-            inb: inb,
-            op: op,
-            out: out,
-        })
+        Ok(Self { ina, inb, op, out })
     }
 }
 
-pub fn day24(args: &[String]) {
-    println!("Day 24");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = (HashMap<String, usize>, Vec<Gate>);
+    type Part1 = usize;
+    type Part2 = Option<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (inputs, gates) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::missing(input, "gates"))?;
+        let re = Regex::new(r"(\w+): (0|1)").unwrap();
+        let inputs: HashMap<String, usize> = inputs
+            .lines()
+            .map(|l| {
+                let caps = error::captures(&re, l)?;
+                Ok((caps[1].to_string(), if &caps[2] == "1" { 1 } else { 0 }))
+            })
+            .collect::<Result<_>>()?;
+        let gates: Vec<Gate> = gates.lines().map(|l| l.parse()).collect::<Result<_>>()?;
+
+        trace!("inputs: {:?}", inputs);
+        trace!("gates: {:?}", gates);
+        Ok((inputs, gates))
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let mut sections = contents.split("\n\n");
-    let inputs: HashMap<String, usize> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| {
-            let re = Regex::new(r"(\w+): (0|1)").unwrap();
-            let caps = re.captures(l).unwrap();
-            (
-                caps[1].to_string(),
-                if caps[2].to_string() == "1" { 1 } else { 0 },
-            )
-        })
-        .collect();
-    let gates: Vec<Gate> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| l.parse().unwrap())
-        .collect();
 
-    // println!("{:?}", starting);
-    // println!("{:?}", inputs);
-    // println!("{:?}", gates);
-
-    let part1 = solve(&inputs, &gates);
-    println!("Part 1: {}", part1);
-
-    // Apply wires fixes
-    let gates = gates
-        .clone()
-        .into_iter()
-        .map(|g| Gate {
-            ina: g.ina,
-            inb: g.inb,
-            op: g.op,
-            out: SWAPS.get(&g.out).unwrap_or(&g.out).to_string(),
-        })
-        .collect();
+    fn part1((inputs, gates): &Self::Input) -> usize {
+        solve(inputs, gates)
+    }
 
-    // build full adders
-    let mut carry = None;
-    for b in 0..=44 {
-        println!("");
-        println!("carry: {:?}", carry);
-        let x = format!("x{:02}", b);
-        let y = format!("y{:02}", b);
-        // let z = format!("z{:02}", b);
-        let ab_xor = find_gate(&gates, &x, &y, "XOR").unwrap();
-        println!("{:?}", ab_xor);
-        let ab_and = find_gate(&gates, &x, &y, "AND").unwrap();
-        println!("{:?}", ab_and);
-        if carry.is_none() {
-            // half adder
-            carry = Some(ab_and.out.clone());
-        } else {
-            // full adder
-            let oc_xor = find_gate(&gates, &ab_xor.clone().out, &carry.clone().unwrap(), "XOR");
-            println!("oc_xor: {:?}", oc_xor);
-
-            let oc_and = find_gate(&gates, &ab_xor.out, &carry.clone().unwrap(), "AND");
-            println!("oc_and: {:?}", oc_and);
-            if oc_and.is_some() {
-                let or = find_gate(&gates, &oc_and.clone().unwrap().out, &ab_and.out, "OR");
-                println!("or: {:?}", or);
-                if or.is_some() {
-                    carry = Some(or.unwrap().out.clone());
-                } else {
-                    carry = None;
+    // Only for a circuit that adds two 45-bit numbers, like the puzzle's.
+    fn part2((_, gates): &Self::Input) -> Option<String> {
+        // Apply wires fixes
+        let gates: Vec<Gate> = gates
+            .clone()
+            .into_iter()
+            .map(|g| Gate {
+                ina: g.ina,
+                inb: g.inb,
+                op: g.op,
+                out: SWAPS.get(&g.out).unwrap_or(&g.out).to_string(),
+            })
+            .collect();
+
+        // build full adders
+        let mut carry = None;
+        for b in 0..=44 {
+            debug!("bit {}: carry {:?}", b, carry);
+            let x = format!("x{:02}", b);
+            let y = format!("y{:02}", b);
+            // let z = format!("z{:02}", b);
+            let ab_xor = find_gate(&gates, &x, &y, "XOR")?;
+            debug!("  {:?}", ab_xor);
+            let ab_and = find_gate(&gates, &x, &y, "AND")?;
+            debug!("  {:?}", ab_and);
+            match &carry {
+                None => {
+                    // half adder
+                    carry = Some(ab_and.out.clone());
+                }
+                Some(c) => {
+                    // full adder
+                    let oc_xor = find_gate(&gates, &ab_xor.out, c, "XOR");
+                    debug!("  oc_xor: {:?}", oc_xor);
+
+                    let oc_and = find_gate(&gates, &ab_xor.out, c, "AND");
+                    debug!("  oc_and: {:?}", oc_and);
+                    if let Some(oc_and) = oc_and {
+                        let or = find_gate(&gates, &oc_and.out, &ab_and.out, "OR");
+                        debug!("  or: {:?}", or);
+                        carry = or.map(|g| g.out);
+                    }
                 }
             }
         }
-    }
 
-    let mut wires: Vec<_> = SWAPS.keys().collect();
-    wires.sort();
-    println!("Part 2: {}", wires.iter().map(|s| s.as_str()).join(","));
+        let mut wires: Vec<_> = SWAPS.keys().collect();
+        wires.sort();
+        Some(wires.iter().map(|s| s.as_str()).join(","))
+    }
 }
 
-fn solve(inputs: &HashMap<String, usize>, gates: &Vec<Gate>) -> usize {
+fn solve(inputs: &HashMap<String, usize>, gates: &[Gate]) -> usize {
     let mut i = inputs.clone();
     let mut allz: Vec<String> = gates
         .iter()
         .map(|g| g.out.clone())
-        .filter(|o| o.starts_with("z"))
+        .filter(|o| o.starts_with('z'))
         .collect();
     allz.sort();
 
@@ -167,7 +156,7 @@ fn solve(inputs: &HashMap<String, usize>, gates: &Vec<Gate>) -> usize {
     allz.iter().rev().fold(0, |v, z| v << 1 | i.get(z).unwrap())
 }
 
-fn find_gate(gates: &Vec<Gate>, ina: &String, inb: &String, op: &str) -> Option<Gate> {
+fn find_gate(gates: &[Gate], ina: &str, inb: &str, op: &str) -> Option<Gate> {
     // gate order doesn't matter
     gates
         .iter()
@@ -1,6 +1,9 @@
+use crate::answer::Answer;
+use crate::error::{self, Error, Result};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::Solution;
 use itertools::iproduct;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -10,23 +13,25 @@ pub enum TumblerType {
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct LockKey {
+pub struct LockKey {
     tumbler: TumblerType,
     pins: Vec<usize>,
 }
 
 impl FromStr for LockKey {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let m: Vec<Vec<char>> = s.lines().map(|l| l.chars().collect()).collect();
-        let t = match m[0][0] {
-            '.' => TumblerType::Lock,
-            '#' => TumblerType::Key,
-            _ => unreachable!(),
+    fn from_str(s: &str) -> Result<Self> {
+        error::chars(s, "#.")?;
+        let m = Grid::parse(s)?;
+        let t = match m.get(Point::ZERO) {
+            Some('.') => TumblerType::Lock,
+            Some('#') => TumblerType::Key,
+            _ => return Err(Error::missing(s, "lock or key schematic")),
         };
-        let p: Vec<_> = (0..m[0].len())
-            .map(|x| (0..m.len()).filter(|y| m[*y][x] == '#').count() - 1)
+        let p: Vec<_> = m
+            .columns()
+            .map(|c| c.filter(|v| **v == '#').count().saturating_sub(1))
             .collect();
         Ok(Self {
             tumbler: t,
@@ -35,30 +40,32 @@ impl FromStr for LockKey {
     }
 }
 
-pub fn day25(args: &[String]) {
-    println!("Day 25");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<LockKey>;
+    type Part1 = usize;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.split("\n\n").map(|s| s.parse()).collect()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let sections = contents.split("\n\n");
-    let lk: Vec<LockKey> = sections.map(|s| s.parse().unwrap()).collect();
+    fn part1(lk: &Self::Input) -> usize {
+        let locks: Vec<_> = filter_type(lk.clone(), TumblerType::Lock);
+        let keys: Vec<_> = filter_type(lk.clone(), TumblerType::Key);
 
-    let locks: Vec<_> = filter_type(lk.clone(), TumblerType::Lock);
-    let keys: Vec<_> = filter_type(lk.clone(), TumblerType::Key);
+        iproduct!(locks, keys)
+            .filter(|(l, k)| (0..l.pins.len()).all(|i| l.pins[i] + k.pins[i] <= 5))
+            .count()
+    }
 
-    let part1 = iproduct!(locks, keys)
-        .filter(|(l, k)| (0..l.pins.len()).all(|i| l.pins[i] + k.pins[i] <= 5))
-        .count();
-    println!("Part 1: {}", part1);
+    // there is no part 2 on the last day
+    fn part2(_lk: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
 }
 
 fn filter_type(lk: Vec<LockKey>, t: TumblerType) -> Vec<LockKey> {
-    lk.into_iter()
-        .filter(|l| l.tumbler == t)
-        .collect()
+    lk.into_iter().filter(|l| l.tumbler == t).collect()
 }
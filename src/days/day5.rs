@@ -1,70 +1,77 @@
+use crate::error::{self, Error, Result};
+use crate::solution::Solution;
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fs;
 
-pub fn day5(args: &[String]) {
-    println!("Day 5");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub struct Day5;
 
-    let mut sections = contents.split("\n\n");
-    let rules: Vec<(_, _)> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| {
-            l.split("|")
-                .map(|v| v.parse::<i32>().unwrap())
-                .collect_tuple()
-                .unwrap()
-        })
-        .collect();
-    let updates: Vec<Vec<_>> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| l.split(",").map(|v| v.parse::<i32>().unwrap()).collect())
-        .collect();
+impl Solution for Day5 {
+    type Input = (Vec<(i32, i32)>, Vec<Vec<i32>>);
+    type Part1 = i32;
+    type Part2 = i32;
 
-    let mut part1 = 0;
-    updates.iter().for_each(|u| {
-        if is_valid(&rules, u) {
-            part1 += u[u.len() / 2];
-        }
-    });
-    println!("Part 1: {}", part1);
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (rules, updates) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::missing(input, "page updates"))?;
+        let rules: Vec<(_, _)> = rules
+            .lines()
+            .map(|l| {
+                l.split('|')
+                    .map(error::number::<i32>)
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .collect_tuple()
+                    .ok_or_else(|| Error::parse(l, "expected a `X|Y` ordering rule"))
+            })
+            .collect::<Result<_>>()?;
+        let updates: Vec<Vec<_>> = updates
+            .lines()
+            .map(|l| l.split(',').map(error::number).collect())
+            .collect::<Result<_>>()?;
+        Ok((rules, updates))
+    }
 
-    let mut part2 = 0;
-    updates.iter().for_each(|u| {
-        let mut u2 = u.clone();
-        u2.sort_by(|a, b| compare(&rules, *a, *b));
-        assert!(is_valid(&rules, &u2));
-        part2 += u2[u2.len() / 2];
-    });
-    println!("Part 2: {}", part2 - part1);
+    fn part1((rules, updates): &Self::Input) -> i32 {
+        let mut part1 = 0;
+        updates.iter().for_each(|u| {
+            if is_valid(rules, u) {
+                part1 += u[u.len() / 2];
+            }
+        });
+        part1
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        let (rules, updates) = input;
+        let mut part2 = 0;
+        updates.iter().for_each(|u| {
+            let mut u2 = u.clone();
+            u2.sort_by(|a, b| compare(rules, *a, *b));
+            assert!(is_valid(rules, &u2));
+            part2 += u2[u2.len() / 2];
+        });
+        // only count the updates that had to be reordered
+        part2 - Self::part1(input)
+    }
 }
 
-pub fn is_valid(rules: &Vec<(i32, i32)>, update: &Vec<i32>) -> bool {
-    (0..update.len()).all(|i| check_right(&rules, &update, i))
+pub fn is_valid(rules: &[(i32, i32)], update: &[i32]) -> bool {
+    (0..update.len()).all(|i| check_right(rules, update, i))
 }
 
-pub fn check_right(rules: &Vec<(i32, i32)>, update: &Vec<i32>, pos: usize) -> bool {
+pub fn check_right(rules: &[(i32, i32)], update: &[i32], pos: usize) -> bool {
     let right_updates: HashSet<i32> = update[pos + 1..].iter().cloned().collect();
-    let right_rules = match_left(&rules, update[pos]);
+    let right_rules = match_left(rules, update[pos]);
     right_updates.is_subset(&right_rules)
 }
 
-pub fn match_left(rules: &Vec<(i32, i32)>, rule: i32) -> HashSet<i32> {
+pub fn match_left(rules: &[(i32, i32)], rule: i32) -> HashSet<i32> {
     rules.iter().filter(|r| r.0 == rule).map(|r| r.1).collect()
 }
 
-pub fn compare(rules: &Vec<(i32, i32)>, a: i32, b: i32) -> Ordering {
+pub fn compare(rules: &[(i32, i32)], a: i32, b: i32) -> Ordering {
     for r in rules {
         if r.0 == a && r.1 == b {
             return Ordering::Less;
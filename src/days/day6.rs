@@ -1,14 +1,9 @@
-use itertools::iproduct;
+use crate::error::{self, Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::parallel::prelude::*;
+use crate::solution::Solution;
 use std::collections::HashSet;
-use std::fs;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Moving {
-    Right,
-    Left,
-    Up,
-    Down,
-}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum State {
@@ -19,14 +14,14 @@ pub enum State {
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 struct Guard {
-    position: (isize, isize),
-    moving: Moving,
-    visited: HashSet<(isize, isize)>,
-    moving_visited: HashSet<(Moving, isize, isize)>,
+    position: Point,
+    moving: Direction,
+    visited: HashSet<Point>,
+    moving_visited: HashSet<(Direction, Point)>,
 }
 
 impl Guard {
-    fn new(position: (isize, isize), moving: Moving) -> Self {
+    fn new(position: Point, moving: Direction) -> Self {
         Self {
             position,
             moving,
@@ -35,83 +30,79 @@ impl Guard {
         }
     }
 
-    fn step(&mut self, map: &Vec<Vec<char>>) -> State {
-        if (0..map.len() as isize).contains(&self.position.0)
-            && (0..map[0].len() as isize).contains(&self.position.1)
-        {
-            let (y, x) = self.position;
-            let (m, dy, dx) = match map[y as usize][x as usize] {
+    fn step(&mut self, map: &Grid<char>) -> State {
+        if let Some(c) = map.get(self.position) {
+            match c {
                 '.' => {
-                    self.visited.insert((y, x));
-                    if !self.moving_visited.insert((self.moving, y, x)) {
+                    self.visited.insert(self.position);
+                    if !self.moving_visited.insert((self.moving, self.position)) {
                         return State::Looping;
                     }
-                    match self.moving {
-                        Moving::Right => (Moving::Right, 0, 1),
-                        Moving::Left => (Moving::Left, 0, -1),
-                        Moving::Up => (Moving::Up, -1, 0),
-                        Moving::Down => (Moving::Down, 1, 0),
-                    }
                 }
-                // turn right
-                '#' => match self.moving {
-                    Moving::Right => (Moving::Down, 1, -1),
-                    Moving::Left => (Moving::Up, -1, 1),
-                    Moving::Up => (Moving::Right, 1, 1),
-                    Moving::Down => (Moving::Left, -1, -1),
-                },
+                // back out of the obstruction and turn right
+                '#' => {
+                    self.position -= self.moving.delta();
+                    self.moving = self.moving.turn_right();
+                }
                 _ => unreachable!(),
             };
-            self.position = (y + dy, x + dx);
-            self.moving = m;
+            self.position += self.moving.delta();
             return State::Moving;
         }
         State::Leaving
     }
 }
 
-pub fn day6(args: &[String]) {
-    println!("Day 6");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = (Grid<char>, Point);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        error::chars(input, ".#^")?;
+        let mut map = Grid::parse(input)?;
+
+        let start = map
+            .find(&'^')
+            .ok_or_else(|| Error::missing(input, "guard `^`"))?;
+        map[start] = '.';
+
+        Ok((map, start))
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let mut map: Vec<Vec<_>> = contents.lines().map(|l| l.chars().collect()).collect();
+    fn part1((map, start): &Self::Input) -> usize {
+        patrol(map, *start).visited.len()
+    }
 
-    let start = iproduct!(0..map.len(), 0..map[0].len())
-        .find(|(y, x)| map[*y][*x] == '^')
-        .unwrap();
-    map[start.0][start.1] = '.';
+    fn part2((map, start): &Self::Input) -> usize {
+        let mut visited_pos = patrol(map, *start).visited;
+        visited_pos.remove(start);
+        visited_pos
+            .par_iter()
+            .filter(|p| {
+                let mut map2 = map.clone();
+                map2[**p] = '#';
+                let mut guard2 = Guard::new(*start, Direction::Up);
+                loop {
+                    match guard2.step(&map2) {
+                        State::Leaving => return false,
+                        State::Looping => return true,
+                        _ => {}
+                    };
+                }
+            })
+            .count()
+    }
+}
 
-    let start = (start.0 as isize, start.1 as isize);
-    let mut guard = Guard::new(start, Moving::Up);
+fn patrol(map: &Grid<char>, start: Point) -> Guard {
+    let mut guard = Guard::new(start, Direction::Up);
     loop {
-        if guard.step(&map) == State::Leaving {
+        if guard.step(map) == State::Leaving {
             break;
         };
     }
-    println!("Part 1: {}", guard.visited.len());
-
-    let mut visited_pos = guard.visited.clone();
-    visited_pos.remove(&start);
-    let part2 = visited_pos
-        .iter()
-        .filter(|p| {
-            let mut map2 = map.clone();
-            map2[p.0 as usize][p.1 as usize] = '#';
-            let mut guard2 = Guard::new(start, Moving::Up);
-            loop {
-                match guard2.step(&map2) {
-                    State::Leaving => return false,
-                    State::Looping => return true,
-                    _ => {}
-                };
-            }
-        })
-        .count();
-    println!("Part 2: {}", part2);
+    guard
 }
@@ -1,31 +1,38 @@
+use crate::error::{self, Error, Result};
+use crate::parallel::prelude::*;
+use crate::solution::Solution;
 use itertools::Itertools;
-use std::fs;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Calibration {
+pub struct Calibration {
     total: usize,
     values: Vec<usize>,
 }
 
 impl FromStr for Calibration {
-    type Err = ParseIntError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (l, r) = s.split(": ").collect_tuple().unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let (l, r) = s
+            .split_once(": ")
+            .ok_or_else(|| Error::missing(s, "`: `"))?;
+        let values: Vec<usize> = r
+            .split_ascii_whitespace()
+            .map(error::number)
+            .collect::<Result<_>>()?;
+        if values.is_empty() {
+            return Err(Error::missing(s, "values"));
+        }
         Ok(Self {
-            total: l.parse().unwrap(),
-            values: r
-                .split_ascii_whitespace()
-                .map(|v| v.parse().unwrap())
-                .collect(),
+            total: error::number(l)?,
+            values,
         })
     }
 }
 
 impl Calibration {
-    fn eval(&self, ops: &Vec<char>) -> usize {
+    fn eval(&self, ops: &[char]) -> usize {
         ops.iter()
             .zip(self.values[1..].iter())
             .fold(self.values[0], |acc, (o, v)| match o {
@@ -37,33 +44,36 @@ impl Calibration {
     }
 }
 
-pub fn day7(args: &[String]) {
-    println!("Day 7");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Calibration>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(|l| l.parse()).collect()
     }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
 
-    let calibrations: Vec<Calibration> = contents.lines().map(|l| l.parse().unwrap()).collect();
+    fn part1(calibrations: &Self::Input) -> usize {
+        solve(calibrations, vec!['+', '*'])
+    }
 
-    println!("Part 1: {}", solve(&calibrations, vec!['+', '*']));
-    println!("Part 2: {}", solve(&calibrations, vec!['+', '*', '|']));
+    fn part2(calibrations: &Self::Input) -> usize {
+        solve(calibrations, vec!['+', '*', '|'])
+    }
 }
 
-fn solve(calibrations: &Vec<Calibration>, ops: Vec<char>) -> usize {
-    let mut solution = 0;
-    for c in calibrations {
-        let c_ops = vec![ops.clone(); c.values.len() - 1];
-        for o in c_ops.into_iter().multi_cartesian_product() {
-            let total = c.eval(&o);
-            if total == c.total {
-                solution += total;
-                break;
-            }
-        }
-    }
-    solution
+fn solve(calibrations: &[Calibration], ops: Vec<char>) -> usize {
+    calibrations
+        .par_iter()
+        .filter(|c| {
+            let c_ops = vec![ops.clone(); c.values.len() - 1];
+            c_ops
+                .into_iter()
+                .multi_cartesian_product()
+                .any(|o| c.eval(&o) == c.total)
+        })
+        .map(|c| c.total)
+        .sum()
 }
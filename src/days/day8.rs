@@ -1,60 +1,56 @@
-use itertools::{iproduct, Itertools};
+use crate::error::Result;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::solution::Solution;
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::fs;
 
-pub fn day8(args: &[String]) {
-    println!("Day 8");
-    if args.len() != 1 {
-        println!("Missing input file");
-        return;
-    }
-    let filename = &args[0];
-    println!("In file {}", filename);
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+type Antennas = HashMap<char, HashSet<Point>>;
+
+pub struct Day8;
 
-    let map: Vec<Vec<_>> = contents.lines().map(|l| l.chars().collect()).collect();
-    let mapsize = (map.len(), map[0].len());
+impl Solution for Day8 {
+    type Input = (Antennas, Grid<char>);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let mut freq_antennas: HashMap<char, HashSet<(usize, usize)>> = HashMap::new();
-    iproduct!(0..mapsize.0, 0..mapsize.1)
-        .filter(|(y, x)| map[*y][*x] != '.')
-        .for_each(|(y, x)| {
-            let freq = freq_antennas.entry(map[y][x]).or_insert(HashSet::new());
-            freq.insert((y, x));
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input)?;
+
+        let mut freq_antennas: Antennas = HashMap::new();
+        map.iter().filter(|(_, c)| **c != '.').for_each(|(p, c)| {
+            let freq = freq_antennas.entry(*c).or_default();
+            freq.insert(p);
         });
+        Ok((freq_antennas, map))
+    }
 
-    println!("Part 1: {}", solve(&freq_antennas, mapsize, true));
-    println!("Part 2: {}", solve(&freq_antennas, mapsize, false));
-}
+    fn part1((freq_antennas, map): &Self::Input) -> usize {
+        solve(freq_antennas, map, true)
+    }
 
-fn in_map(mapsize: (usize, usize), pos: (isize, isize)) -> bool {
-    (0..mapsize.0 as isize).contains(&pos.0) && (0..mapsize.1 as isize).contains(&pos.1)
+    fn part2((freq_antennas, map): &Self::Input) -> usize {
+        solve(freq_antennas, map, false)
+    }
 }
 
-fn solve(
-    freq_antennas: &HashMap<char, HashSet<(usize, usize)>>,
-    mapsize: (usize, usize),
-    part1: bool,
-) -> usize {
+fn solve(freq_antennas: &Antennas, map: &Grid<char>, part1: bool) -> usize {
     let mut antinodes = HashSet::new();
     let partrange = if part1 { 1..2 } else { 0..isize::MAX };
-    for (_freq, antennas) in freq_antennas {
+    for antennas in freq_antennas.values() {
         for ant in antennas.iter().combinations(2) {
-            let (dy, dx) = (
-                ant[0].0 as isize - ant[1].0 as isize,
-                ant[0].1 as isize - ant[1].1 as isize,
-            );
+            let d = *ant[0] - *ant[1];
             for i in partrange.clone() {
-                let a0 = (ant[0].0 as isize + i * dy, ant[0].1 as isize + i * dx);
-                if in_map(mapsize, a0) {
+                let a0 = *ant[0] + d * i;
+                if map.contains(a0) {
                     antinodes.insert(a0);
                 } else {
                     break;
                 }
             }
             for i in partrange.clone() {
-                let a1 = (ant[1].0 as isize - i * dy, ant[1].1 as isize - i * dx);
-                if in_map(mapsize, a1) {
+                let a1 = *ant[1] - d * i;
+                if map.contains(a1) {
                     antinodes.insert(a1);
                 } else {
                     break;
@@ -1,13 +1,7 @@
+use crate::solution::Day;
+
 // pub mod utils;
 pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
 pub mod day10;
 pub mod day11;
 pub mod day12;
@@ -18,9 +12,57 @@ pub mod day16;
 pub mod day17;
 pub mod day18;
 pub mod day19;
+pub mod day2;
 pub mod day20;
-//pub mod day21;
+pub mod day21;
 pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+lazy_static! {
+    static ref DAYS: Vec<Day> = vec![
+        Day::new::<day1::Day1>(1),
+        Day::new::<day2::Day2>(2),
+        Day::new::<day3::Day3>(3),
+        Day::new::<day4::Day4>(4),
+        Day::new::<day5::Day5>(5),
+        Day::new::<day6::Day6>(6),
+        Day::new::<day7::Day7>(7),
+        Day::new::<day8::Day8>(8),
+        Day::new::<day9::Day9>(9),
+        Day::new::<day10::Day10>(10),
+        Day::new::<day11::Day11>(11),
+        Day::new::<day12::Day12>(12),
+        Day::new::<day13::Day13>(13),
+        Day::new::<day14::Day14>(14),
+        Day::new::<day15::Day15>(15),
+        Day::new::<day16::Day16>(16),
+        Day::new::<day17::Day17>(17),
+        Day::new::<day18::Day18>(18),
+        Day::new::<day19::Day19>(19),
+        Day::new::<day20::Day20>(20),
+        Day::new::<day21::Day21>(21),
+        Day::new::<day22::Day22>(22),
+        Day::new::<day23::Day23>(23),
+        Day::new::<day24::Day24>(24),
+        Day::new::<day25::Day25>(25),
+    ];
+}
+
+/// All of the implemented days, in order.
+pub fn all() -> &'static [Day] {
+    &DAYS
+}
+
+/// Look up a day by name, e.g. "day7".
+pub fn find(name: &str) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.name() == name)
+}
@@ -0,0 +1,118 @@
+use regex::{Captures, Regex};
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input file could not be read.
+    Io(String, io::Error),
+    /// The puzzle input is malformed.
+    Parse(ParseError),
+    /// The command line arguments are invalid.
+    Usage(String),
+    /// A part run in a worker process took longer than it was allowed.
+    Timeout(Duration),
+    /// A worker process failed, e.g. because the solver panicked.
+    Worker(String),
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    // Address of the offending text. Puzzle input is always parsed from
+    // slices of the original contents, so `locate` can turn this back into
+    // a line and column once the whole input is known.
+    at: usize,
+}
+
+impl Error {
+    /// A parse error for `text`, which should be a slice of the puzzle input.
+    pub fn parse(text: &str, message: impl Into<String>) -> Self {
+        Error::Parse(ParseError {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            at: text.as_ptr() as usize,
+        })
+    }
+
+    /// A parse error for something that should follow `text` but is missing.
+    pub fn missing(text: &str, what: &str) -> Self {
+        Self::parse(&text[text.len()..], format!("missing {}", what))
+    }
+
+    /// Fill in the file, line and column of a parse error from the `input`
+    /// that was being parsed.
+    pub fn locate(self, file: &str, input: &str) -> Self {
+        match self {
+            Error::Parse(mut e) => {
+                e.file = Some(file.to_string());
+                let offset = e.at.wrapping_sub(input.as_ptr() as usize);
+                if offset <= input.len() {
+                    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+                    e.line = Some(input[..offset].matches('\n').count() + 1);
+                    e.column = Some(input[line_start..offset].chars().count() + 1);
+                }
+                Error::Parse(e)
+            }
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(file, e) => write!(f, "{}: {}", file, e),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Timeout(limit) => write!(f, "timed out after {:.2?}", limit),
+            Error::Worker(message) => write!(f, "{}", message),
+            Error::Parse(e) => {
+                if let Some(file) = &e.file {
+                    write!(f, "{}:", file)?;
+                }
+                if let (Some(line), Some(column)) = (e.line, e.column) {
+                    write!(f, "{}:{}:", line, column)?;
+                }
+                if e.file.is_some() {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", e.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parse a number from a slice of the puzzle input.
+pub fn number<T: FromStr>(s: &str) -> Result<T> {
+    s.parse()
+        .map_err(|_| Error::parse(s, format!("invalid number `{}`", s)))
+}
+
+/// Match `re` against a slice of the puzzle input.
+pub fn captures<'a>(re: &Regex, s: &'a str) -> Result<Captures<'a>> {
+    re.captures(s)
+        .ok_or_else(|| Error::parse(s, format!("expected `{}`", re.as_str())))
+}
+
+/// Check that a slice of the puzzle input only contains characters from
+/// `allowed`, ignoring line breaks.
+pub fn chars(s: &str, allowed: &str) -> Result<()> {
+    match s
+        .char_indices()
+        .find(|(_, c)| *c != '\n' && !allowed.contains(*c))
+    {
+        Some((i, c)) => Err(Error::parse(&s[i..], format!("unexpected `{}`", c))),
+        None => Ok(()),
+    }
+}
@@ -1,4 +1,19 @@
 #[macro_use]
 extern crate lazy_static;
 
-pub mod days;
\ No newline at end of file
+pub mod answer;
+pub mod batch;
+pub mod bench;
+pub mod cache;
+pub mod days;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod parallel;
+pub mod params;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod verify;
+pub mod worker;
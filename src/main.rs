@@ -1,45 +1,681 @@
 #![recursion_limit = "64"]
 
-use std::env;
+use aoc24::answer::Answer;
+use aoc24::batch;
+use aoc24::bench;
+use aoc24::cache::{self, Cache};
 use aoc24::days;
+use aoc24::days::day17::{self, Computer, Debugger};
+use aoc24::error::{Error, Result};
+use aoc24::parallel;
+use aoc24::params::{self, Params};
+use aoc24::runner;
+use aoc24::scaffold;
+use aoc24::solution::Day;
+use aoc24::verify::{self, Outcome};
+use aoc24::worker::PANICKED;
+use env_logger::Env;
+use log::{debug, LevelFilter};
+use serde_json::{json, Value};
+use std::env;
+use std::io::{self, Write};
+use std::panic;
+use std::path::Path;
+use std::time::Duration;
+
+// Commands for working with day 17 programs.
+const TOOLS: &[&str] = &["disassemble", "assemble", "decompile"];
+
+// Whether the command is one of the day 17 tools, which print programs or
+// run a session of their own, so shouldn't start with a banner.
+fn is_tool(args: &[String]) -> bool {
+    match args {
+        [_, day, debug, ..] if day == "day17" && debug == "debug" => true,
+        [_, command, ..] => TOOLS.contains(&command.as_str()),
+        _ => false,
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Format {
+    Text,
+    /// One JSON object per line for each run.
+    Json,
+}
 
 fn main() {
-    println!("Advent of Code 2024");
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let format = match take_option(&mut args, "--format") {
+        Ok(None) => Format::Text,
+        Ok(Some(f)) if f == "text" => Format::Text,
+        Ok(Some(f)) if f == "json" => Format::Json,
+        Ok(Some(f)) => exit(Error::Usage(format!("Unknown format: {}", f))),
+        Err(e) => exit(e),
+    };
+    match take_option(&mut args, "--jobs") {
+        Ok(None) => {}
+        Ok(Some(n)) => match n.parse() {
+            Ok(n) => parallel::set_jobs(n).unwrap_or_else(|e| exit(e)),
+            Err(_) => exit(Error::Usage(format!("Invalid jobs: {}", n))),
+        },
+        Err(e) => exit(e),
+    }
+    init_logging(take_verbosity(&mut args));
+    report_panics();
+    if format == Format::Text && !is_tool(&args) {
+        println!("Advent of Code 2024");
+    }
     if args.len() < 2 {
-        println!("Missing input day");
+        exit(Error::Usage("Missing input day".to_string()));
+    }
+    let (command, cmdargs) = (&args[1], args[2..].to_vec());
+    let runs_days = command == "all" || (days::find(command).is_some() && !is_tool(&args));
+    if format == Format::Json && !runs_days {
+        exit(Error::Usage(format!(
+            "--format json only works with dayN and all, not {}",
+            command
+        )));
+    }
+    let result = match command.as_str() {
+        "all" => all(cmdargs, format),
+        "bench" => bench(cmdargs),
+        "verify" => verify(cmdargs),
+        "batch" => batch(cmdargs),
+        "new" => new(&cmdargs),
+        "disassemble" => disassemble(&cmdargs),
+        "assemble" => assemble(&cmdargs),
+        "decompile" => decompile(&cmdargs),
+        "day17" if cmdargs.first().is_some_and(|a| a == "debug") => debug(&cmdargs[1..]),
+        day => match days::find(day) {
+            Some(solution) => run(solution, cmdargs, format),
+            None => Err(Error::Usage(format!("Unknown day: {}", day))),
+        },
+    };
+    if let Err(e) = result {
+        exit(e);
+    }
+}
+
+// A solver that panics, e.g. on an input it wasn't written for, is reported
+// like any other error rather than with a backtrace. Where it panicked is
+// only logged.
+fn report_panics() {
+    panic::set_hook(Box::new(|info| {
+        if let Some(location) = info.location() {
+            debug!("Panicked at {}", location);
+        }
+        eprintln!(
+            "Error: {}{}",
+            PANICKED,
+            info.payload_as_str().unwrap_or("unknown panic")
+        );
         std::process::exit(1);
+    }));
+}
+
+fn exit(e: Error) -> ! {
+    eprintln!("Error: {}", e);
+    std::process::exit(1);
+}
+
+// dayN [file|-] [--part 1|2] [--timeout SECONDS] [--width N] [--height N] ...
+fn run(solution: &Day, mut args: Vec<String>, format: Format) -> Result<()> {
+    let overrides = take_params(&mut args)?;
+    let timeout = take_timeout(&mut args)?;
+    let parts = match take_option(&mut args, "--part")?.as_deref() {
+        None => vec![1, 2],
+        Some("1") => vec![1],
+        Some("2") => vec![2],
+        Some(p) => return Err(Error::Usage(format!("Invalid part: {}", p))),
+    };
+    let filename = match &args[..] {
+        [] => runner::STDIN,
+        [filename] => filename,
+        _ => return Err(Error::Usage(format!("Unexpected argument: {}", args[1]))),
+    };
+    if format == Format::Json {
+        let run = input_params(filename, &overrides).and_then(|params| {
+            runner::run_parts(solution, filename, &params, &parts, timeout, None)
+        });
+        println!("{}", run_json(solution, filename, &run));
+        let (_, parts) = run?;
+        return check_parts(parts.iter().map(|(_, part)| part));
+    }
+
+    println!("Day {}", solution.number);
+    println!("In file {}", runner::display_path(filename));
+    let params = input_params(filename, &overrides)?;
+    let (_, parts) = runner::run_parts(solution, filename, &params, &parts, timeout, None)?;
+    for (part, result) in &parts {
+        match result {
+            Ok(p) => print_answer(*part, p.answer.clone()),
+            Err(e) => println!("Part {}: {}", part, failure(e)),
+        }
+    }
+    check_parts(parts.iter().map(|(_, part)| part))
+}
+
+fn check_parts<'a>(parts: impl Iterator<Item = &'a Result<runner::Part>>) -> Result<()> {
+    match parts.filter(|p| p.is_err()).count() {
+        0 => Ok(()),
+        1 => Err(Error::Usage("1 part failed".to_string())),
+        n => Err(Error::Usage(format!("{} parts failed", n))),
+    }
+}
+
+// How a part that failed is shown in place of its answer.
+fn failure(e: &Error) -> String {
+    match e {
+        Error::Timeout(_) => "TIMEOUT".to_string(),
+        e => format!("Error: {}", e),
     }
-    let (day, dayargs) = (&args[1], &args[2..]);
-    match day.as_ref() {
-        "day1" => days::day1::day1(dayargs),
-        "day2" => days::day2::day2(dayargs),
-        "day3" => days::day3::day3(dayargs),
-        "day4" => days::day4::day4(dayargs),
-        "day5" => days::day5::day5(dayargs),
-        "day6" => days::day6::day6(dayargs),
-        "day7" => days::day7::day7(dayargs),
-        "day8" => days::day8::day8(dayargs),
-        "day9" => days::day9::day9(dayargs),
-        "day10" => days::day10::day10(dayargs),
-        "day11" => days::day11::day11(dayargs),
-        "day12" => days::day12::day12(dayargs),
-        "day13" => days::day13::day13(dayargs),
-        "day14" => days::day14::day14(dayargs),
-        "day15" => days::day15::day15(dayargs),
-        "day16" => days::day16::day16(dayargs),
-        "day17" => days::day17::day17(dayargs),
-        "day18" => days::day18::day18(dayargs),
-        "day19" => days::day19::day19(dayargs),
-        "day20" => days::day20::day20(dayargs),
-        //"day21" => days::day21::day21(dayargs),
-        "day22" => days::day22::day22(dayargs),
-        "day23" => days::day23::day23(dayargs),
-        "day24" => days::day24::day24(dayargs),
-        "day25" => days::day25::day25(dayargs),
+}
+
+// A run of one day as a JSON object. Times are in nanoseconds.
+fn run_json(day: &Day, path: &str, run: &Result<runner::Run>) -> Value {
+    let mut json = json!({
+        "day": day.number,
+        "input": runner::display_path(path),
+    });
+    match run {
+        Ok((parse_time, parts)) => {
+            json["parse_ns"] = json!(parse_time.as_nanos() as u64);
+            json["parts"] = parts
+                .iter()
+                .map(|(p, part)| match part {
+                    Ok(part) => {
+                        let answer = match &part.answer {
+                            Answer::Int(v) => json!(v),
+                            Answer::Text(s) => json!(s),
+                            Answer::Unsolved => Value::Null,
+                        };
+                        let time = part.time.as_nanos() as u64;
+                        json!({"part": p, "answer": answer, "time_ns": time, "error": null})
+                    }
+                    Err(e) => json!({"part": p, "answer": null, "error": e.to_string()}),
+                })
+                .collect();
+            json["error"] = Value::Null;
+        }
+        Err(e) => json["error"] = json!(e.to_string()),
+    }
+    json
+}
+
+fn print_answer(part: u32, answer: Answer) {
+    if answer != Answer::Unsolved {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+// Run every day against its `dayN.txt` in the data directory and print a
+// table of the answers and timings.
+fn all(mut args: Vec<String>, format: Format) -> Result<()> {
+    let timeout = take_timeout(&mut args)?;
+    let cache = take_cache(&mut args);
+    let dir = Path::new(args.first().map_or("data", |d| d.as_str()));
+    let run = |day: &Day, path: &str| {
+        let params = Params::for_input(path)?;
+        runner::run_parts(day, path, &params, &[1, 2], timeout, cache.as_ref())
+    };
+    if format == Format::Json {
+        for day in days::all() {
+            let path = dir.join(format!("{}.txt", day.name()));
+            let path = path.to_string_lossy();
+            println!("{}", run_json(day, &path, &run(day, &path)));
+        }
+        return Ok(());
+    }
+
+    let mut rows = vec![row(&["Day", "Parse", "Part 1", "Time", "Part 2", "Time"])];
+    let mut total = Duration::ZERO;
+    for day in days::all() {
+        let path = dir.join(format!("{}.txt", day.name()));
+        if !path.exists() {
+            rows.push(vec![day.name(), "-".to_string(), "no input".to_string()]);
+            continue;
+        }
+        match run(day, &path.to_string_lossy()) {
+            Ok((parse_time, parts)) => {
+                total += parse_time;
+                let mut r = vec![day.name(), format_duration(parse_time)];
+                for (_, part) in parts {
+                    match part {
+                        Ok(part) => {
+                            total += part.time;
+                            r.push(part.answer.to_string());
+                            r.push(format_duration(part.time));
+                        }
+                        Err(e) => {
+                            r.push(failure(&e));
+                            r.push("-".to_string());
+                        }
+                    }
+                }
+                rows.push(r);
+            }
+            Err(e) => rows.push(vec![day.name(), "-".to_string(), format!("Error: {}", e)]),
+        }
+    }
+    let mut last = row(&["Total", "", "", "", ""]);
+    last.push(format_duration(total));
+    rows.push(last);
+    print_table(&rows);
+    Ok(())
+}
+
+// bench dayN [file] [--iterations N] [--save baseline.csv] [--compare baseline.csv]
+fn bench(mut args: Vec<String>) -> Result<()> {
+    let iterations = match take_option(&mut args, "--iterations")? {
+        Some(n) => n
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| Error::Usage(format!("Invalid iteration count: {}", n)))?,
+        None => 10,
+    };
+    let overrides = take_params(&mut args)?;
+    let save = take_option(&mut args, "--save")?;
+    let compare = take_option(&mut args, "--compare")?;
+    let (day, path) = match &args[..] {
+        [day] => (day, format!("data/{}.txt", day)),
+        [day, path] => (day, path.clone()),
         _ => {
-            println!("Unknown day: {}", day);
+            return Err(Error::Usage(
+                "Usage: bench dayN [file] [--iterations N] [--save FILE] [--compare FILE]"
+                    .to_string(),
+            ))
+        }
+    };
+    let day = days::find(day).ok_or_else(|| Error::Usage(format!("Unknown day: {}", day)))?;
+
+    let baseline = compare.map(|c| bench::read_baseline(&c)).transpose()?;
+    if let Some(baseline) = &baseline {
+        if !baseline
+            .keys()
+            .any(|(d, p, _)| *d == day.name() && *p == path)
+        {
+            return Err(Error::Usage(format!(
+                "The baseline has no timings of {} with {}",
+                day.name(),
+                path
+            )));
+        }
+    }
+
+    println!(
+        "Benchmarking {} with {} ({} iterations)",
+        day.name(),
+        path,
+        iterations
+    );
+    let params = input_params(&path, &overrides)?;
+    let stats = bench::bench(day, &path, &params, iterations)?;
+
+    let mut rows = vec![row(&["Step", "Min", "Median", "Mean", "Std dev"])];
+    if baseline.is_some() {
+        rows[0].push("Baseline".to_string());
+        rows[0].push("Change".to_string());
+    }
+    for (step, s) in bench::STEPS.iter().zip(&stats) {
+        let mut r = vec![step.to_string()];
+        r.extend([s.min, s.median, s.mean, s.stddev].map(format_duration));
+        if let Some(base) = baseline
+            .as_ref()
+            .and_then(|b| b.get(&(day.name(), path.clone(), step.to_string())))
+        {
+            let change = s.median.as_secs_f64() / base.median.as_secs_f64() - 1.0;
+            r.push(format_duration(base.median));
+            r.push(format!("{:+.1}%", change * 100.0));
+        }
+        rows.push(r);
+    }
+    print_table(&rows);
+
+    if let Some(save) = save {
+        let mut saved = if Path::new(&save).exists() {
+            bench::read_baseline(&save)?
+        } else {
+            bench::Baseline::new()
+        };
+        for (step, s) in bench::STEPS.iter().zip(stats) {
+            saved.insert((day.name(), path.clone(), step.to_string()), s);
+        }
+        bench::write_baseline(&save, &saved)?;
+        println!("Saved baseline to {}", save);
+    }
+    Ok(())
+}
+
+// batch dayN file... [--budget SECONDS]: run one day against many inputs
+// and flag any that fail, panic or take longer than the budget.
+fn batch(mut args: Vec<String>) -> Result<()> {
+    let budget = match take_option(&mut args, "--budget")? {
+        Some(s) => seconds("budget", &s)?,
+        None => batch::DEFAULT_BUDGET,
+    };
+    let overrides = take_params(&mut args)?;
+    let [day, paths @ ..] = &args[..] else {
+        return Err(Error::Usage(
+            "Usage: batch dayN file... [--budget SECONDS]".to_string(),
+        ));
+    };
+    let day = days::find(day).ok_or_else(|| Error::Usage(format!("Unknown day: {}", day)))?;
+
+    let outcomes = batch::batch(day, paths, budget, |path| input_params(path, &overrides));
+    let mut rows = vec![row(&["Input", "Part 1", "Part 2", "Time", "Status"])];
+    for (path, outcome) in paths.iter().zip(&outcomes) {
+        rows.push(match outcome {
+            batch::Outcome::Ran((parse_time, parts)) => {
+                let mut row = vec![path.clone()];
+                let mut time = *parse_time;
+                for (_, part) in parts {
+                    row.push(match part {
+                        Ok(p) => {
+                            time += p.time;
+                            p.answer.to_string()
+                        }
+                        Err(e) => failure(e),
+                    });
+                }
+                row.push(format_duration(time));
+                row.push(if parts.iter().any(|(_, p)| p.is_err()) {
+                    "failed".to_string()
+                } else if outcome.flagged(budget) {
+                    format!("over budget of {}", format_duration(budget))
+                } else {
+                    "ok".to_string()
+                });
+                row
+            }
+            batch::Outcome::Error(e) => vec![path.clone(), format!("Error: {}", e)],
+        });
+    }
+    print_table(&rows);
+
+    let flagged = outcomes.iter().filter(|o| o.flagged(budget)).count();
+    println!("{} inputs, {} flagged", outcomes.len(), flagged);
+    if flagged > 0 {
+        return Err(Error::Usage(format!("{} inputs flagged", flagged)));
+    }
+    Ok(())
+}
+
+// new dayN: start a new day from the template.
+fn new(args: &[String]) -> Result<()> {
+    let number = match args {
+        [day] => day.strip_prefix("day").and_then(|n| n.parse().ok()),
+        _ => None,
+    }
+    .ok_or_else(|| Error::Usage("Usage: new dayN".to_string()))?;
+    for path in scaffold::new_day(Path::new("."), number)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// disassemble [file|-]: print a day 17 program as mnemonics, in a form
+// that `assemble` reads back.
+fn disassemble(args: &[String]) -> Result<()> {
+    let computer = read_computer(tool_input("disassemble", args)?)?;
+    for (name, value) in day17::REGISTERS.iter().zip(computer.registers()) {
+        println!(".{} {}", name, value);
+    }
+    println!();
+    print!("{}", day17::disassemble(computer.program()));
+    Ok(())
+}
+
+// assemble [file|-]: turn mnemonic source into a day 17 input.
+fn assemble(args: &[String]) -> Result<()> {
+    let filename = tool_input("assemble", args)?;
+    let contents = runner::read_input(filename)?;
+    let computer = day17::assemble(&contents)
+        .map_err(|e| e.locate(runner::display_path(filename), &contents))?;
+    print!("{}", computer);
+    Ok(())
+}
+
+// decompile [file|-]: print what one time around a day 17 program's loop
+// outputs, as code.
+fn decompile(args: &[String]) -> Result<()> {
+    let computer = read_computer(tool_input("decompile", args)?)?;
+    let iteration = day17::decompile(computer.program()).ok_or_else(|| {
+        Error::Usage(
+            "Only programs whose only jump is a final `jnz 0`, \
+             and that don't use combo operand 7, can be decompiled"
+                .to_string(),
+        )
+    })?;
+    print!("{}", iteration);
+    Ok(())
+}
+
+// day17 debug file: step through a day 17 program, with commands read from
+// stdin.
+fn debug(args: &[String]) -> Result<()> {
+    let filename = match args {
+        [filename] if filename != runner::STDIN => filename,
+        _ => return Err(Error::Usage("Usage: day17 debug file".to_string())),
+    };
+    let mut debugger = Debugger::new(read_computer(filename)?);
+    println!("{}", debugger.command("print")?);
+    println!("Type h for help");
+    let mut line = String::new();
+    loop {
+        print!("(day17) ");
+        io::stdout()
+            .flush()
+            .map_err(|e| Error::Io("stdout".to_string(), e))?;
+        line.clear();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| Error::Io("stdin".to_string(), e))?;
+        match line.trim() {
+            _ if read == 0 => break,
+            "q" | "quit" => break,
+            command => match debugger.command(command) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => println!("{}", output),
+                Err(e) => println!("Error: {}", e),
+            },
+        }
+    }
+    println!();
+    Ok(())
+}
+
+fn read_computer(filename: &str) -> Result<Computer> {
+    let contents = runner::read_input(filename)?;
+    contents
+        .parse()
+        .map_err(|e: Error| e.locate(runner::display_path(filename), &contents))
+}
+
+// The one input file of a day 17 tool, from stdin by default.
+fn tool_input<'a>(command: &str, args: &'a [String]) -> Result<&'a str> {
+    match args {
+        [] => Ok(runner::STDIN),
+        [filename] => Ok(filename),
+        _ => Err(Error::Usage(format!("Usage: {} [file|-]", command))),
+    }
+}
+
+// Check every input in the data directory against its `answers.toml`.
+fn verify(mut args: Vec<String>) -> Result<()> {
+    let cache = take_cache(&mut args);
+    let dir = Path::new(args.first().map_or("data", |d| d.as_str()));
+    let manifest_path = dir.join("answers.toml");
+    let manifest = verify::read_manifest(&manifest_path.to_string_lossy())?;
+
+    // every dayN*.txt input, ordered by day
+    let mut names: Vec<String> = dir
+        .read_dir()
+        .map_err(|e| Error::Io(dir.to_string_lossy().to_string(), e))?
+        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+        .filter_map(|f| f.strip_suffix(".txt").map(|n| n.to_string()))
+        .filter(|n| verify::day_for(n).is_some())
+        .chain(manifest.keys().cloned())
+        .collect();
+    names.sort_by_key(|n| (verify::day_for(n).map(|d| d.number), n.clone()));
+    names.dedup();
+
+    let mut rows = vec![row(&["Input", "Part", "Status"])];
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for name in names {
+        let Some(expected) = manifest.get(&name) else {
+            missing += 1;
+            rows.push(vec![name, "-".to_string(), "MISSING".to_string()]);
+            continue;
+        };
+        for (part, outcome) in verify::verify(dir, &name, expected, cache.as_ref()) {
+            let status = match outcome {
+                Outcome::Pass => {
+                    passed += 1;
+                    "PASS".to_string()
+                }
+                Outcome::Fail { expected, actual } => {
+                    failed += 1;
+                    format!("FAIL expected {}, got {}", expected, actual)
+                }
+                Outcome::Error(e) => {
+                    failed += 1;
+                    format!("FAIL {}", e)
+                }
+            };
+            rows.push(vec![name.clone(), part.to_string(), status]);
+        }
+    }
+    print_table(&rows);
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        return Err(Error::Usage(format!("{} checks failed", failed)));
+    }
+    Ok(())
+}
+
+// Remove any `--width N` style puzzle parameters from the arguments.
+fn take_params(args: &mut Vec<String>) -> Result<Vec<(&'static str, i64)>> {
+    let mut params = vec![];
+    for name in params::NAMES {
+        if let Some(v) = take_option(args, &format!("--{}", name))? {
+            let v = v
+                .parse()
+                .map_err(|_| Error::Usage(format!("Invalid {}: {}", name, v)))?;
+            params.push((name, v));
         }
+    }
+    Ok(params)
+}
+
+// The parameters from the input's sidecar file, overridden by those given
+// on the command line.
+fn input_params(path: &str, overrides: &[(&str, i64)]) -> Result<Params> {
+    let mut params = Params::for_input(path)?;
+    for (name, value) in overrides {
+        params.set(name, *value);
+    }
+    Ok(params)
+}
+
+// Remove `--no-cache` from the arguments, and return the cache to use
+// unless it was there.
+fn take_cache(args: &mut Vec<String>) -> Option<Cache> {
+    let len = args.len();
+    args.retain(|a| a != "--no-cache");
+    (args.len() == len).then(|| Cache::new(cache::DEFAULT_DIR))
+}
+
+// Remove `--timeout SECONDS` from the arguments and return the timeout.
+fn take_timeout(args: &mut Vec<String>) -> Result<Option<Duration>> {
+    take_option(args, "--timeout")?
+        .map(|s| seconds("timeout", &s))
+        .transpose()
+}
+
+fn seconds(name: &str, s: &str) -> Result<Duration> {
+    s.parse()
+        .ok()
+        .and_then(|s| Duration::try_from_secs_f64(s).ok())
+        .ok_or_else(|| Error::Usage(format!("Invalid {}: {}", name, s)))
+}
+
+// Remove `name value` from the arguments and return the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(i) = args.iter().position(|a| a == name) else {
+        return Ok(None);
     };
+    args.remove(i);
+    if i < args.len() {
+        Ok(Some(args.remove(i)))
+    } else {
+        Err(Error::Usage(format!("Missing value for {}", name)))
+    }
 }
 
+// Remove every `-v` and `-vv` from the arguments and return the total
+// number of `v`s.
+fn take_verbosity(args: &mut Vec<String>) -> usize {
+    let mut verbosity = 0;
+    args.retain(|a| match a.as_str() {
+        "-v" => {
+            verbosity += 1;
+            false
+        }
+        "-vv" => {
+            verbosity += 2;
+            false
+        }
+        _ => true,
+    });
+    verbosity
+}
+
+// Log to stderr, so the answers on stdout stay clean. Only warnings are
+// shown unless `RUST_LOG` says otherwise, while `-v` adds the days' debug
+// output and `-vv` their tracing.
+fn init_logging(verbosity: usize) {
+    let mut logger = env_logger::Builder::from_env(Env::default().default_filter_or("warn"));
+    match verbosity {
+        0 => {}
+        1 => {
+            logger.filter_module("aoc24", LevelFilter::Debug);
+        }
+        _ => {
+            logger.filter_module("aoc24", LevelFilter::Trace);
+        }
+    }
+    logger.format_timestamp(None).init();
+}
+
+fn row(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|c| c.to_string()).collect()
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.2?}", d)
+}
+
+// Print left-aligned columns. A row with fewer cells than the others lets
+// its last cell run on past the remaining columns.
+fn print_table(rows: &[Vec<String>]) {
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| {
+            rows.iter()
+                .filter(|r| c + 1 < r.len() || r.len() == columns)
+                .filter_map(|r| r.get(c))
+                .map(|v| v.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in rows {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(v, w)| format!("{:w$}", v, w = w))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
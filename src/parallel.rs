@@ -0,0 +1,73 @@
+use crate::error::{Error, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// The number of threads from `set_jobs`, or 0 if it hasn't been called.
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// `par_iter` for the hot loops of the slower days. With the `parallel`
+/// feature this is rayon's, and without it a plain `iter`, so the days are
+/// written the same way either way.
+#[cfg(feature = "parallel")]
+pub mod prelude {
+    pub use rayon::prelude::*;
+}
+
+/// The single-threaded stand-in for rayon's prelude.
+#[cfg(not(feature = "parallel"))]
+pub mod prelude {
+    /// A stand-in for rayon's trait of the same name, which iterates over
+    /// the collection on the current thread.
+    pub trait IntoParallelRefIterator<'data> {
+        type Iter: Iterator;
+
+        fn par_iter(&'data self) -> Self::Iter;
+    }
+
+    impl<'data, C: 'data + ?Sized> IntoParallelRefIterator<'data> for C
+    where
+        &'data C: IntoIterator,
+    {
+        type Iter = <&'data C as IntoIterator>::IntoIter;
+
+        fn par_iter(&'data self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+}
+
+/// Use `jobs` threads for everything run in parallel from now on. This can
+/// only be called once, before anything has run in parallel.
+#[cfg(feature = "parallel")]
+pub fn set_jobs(jobs: usize) -> Result<()> {
+    if jobs == 0 {
+        return Err(Error::Usage("--jobs must be at least 1".to_string()));
+    }
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build_global()
+        .map_err(|e| Error::Usage(format!("Can't start {} jobs: {}", jobs, e)))?;
+    JOBS.store(jobs, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Without the `parallel` feature everything runs on one thread.
+#[cfg(not(feature = "parallel"))]
+pub fn set_jobs(jobs: usize) -> Result<()> {
+    match jobs {
+        1 => {
+            JOBS.store(jobs, Ordering::Relaxed);
+            Ok(())
+        }
+        _ => Err(Error::Usage(
+            "--jobs needs the `parallel` feature".to_string(),
+        )),
+    }
+}
+
+/// The number of threads given to `set_jobs`, if it was called.
+pub fn jobs() -> Option<usize> {
+    match JOBS.load(Ordering::Relaxed) {
+        0 => None,
+        jobs => Some(jobs),
+    }
+}
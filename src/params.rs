@@ -0,0 +1,69 @@
+use crate::error::{Error, Result};
+use crate::runner;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The parameters that can be set on the command line as `--name value`.
+pub const NAMES: [&str; 5] = ["width", "height", "bytes", "min-save", "min-save-part2"];
+
+/// Puzzle parameters that aren't part of the input itself, such as the size
+/// of a grid, which differ between the examples and the real puzzle. A day
+/// uses the real puzzle's value for anything that isn't set.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Params(BTreeMap<String, i64>);
+
+impl Params {
+    /// The parameters for the input at `path`, read from the sidecar file
+    /// next to it (e.g. `data/day14example.toml` for
+    /// `data/day14example.txt`) if there is one:
+    ///
+    /// ```toml
+    /// width = 11
+    /// height = 7
+    /// ```
+    pub fn for_input(path: &str) -> Result<Params> {
+        let sidecar = Path::new(path).with_extension("toml");
+        if path == runner::STDIN || !sidecar.exists() {
+            return Ok(Params::default());
+        }
+        let sidecar = sidecar.to_string_lossy();
+        let contents = runner::read_input(&sidecar)?;
+        let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| {
+            let at = e.span().map_or(0, |s| s.start);
+            Error::parse(&contents[at..], e.message()).locate(&sidecar, &contents)
+        })?;
+
+        let mut params = Params::default();
+        for (name, value) in table {
+            let value = value.as_integer().ok_or_else(|| {
+                Error::Usage(format!("{}: `{}` must be an integer", sidecar, name))
+            })?;
+            params.set(&name, value);
+        }
+        Ok(params)
+    }
+
+    /// The parameters that are set, as they would be given on the command
+    /// line.
+    pub fn to_args(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(name, _)| NAMES.contains(&name.as_str()))
+            .flat_map(|(name, value)| [format!("--{}", name), value.to_string()])
+            .collect()
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    /// The value of `name`, or `default` if it isn't set.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str, default: T) -> Result<T> {
+        match self.0.get(name) {
+            Some(&v) => {
+                T::try_from(v).map_err(|_| Error::Usage(format!("Invalid {}: {}", name, v)))
+            }
+            None => Ok(default),
+        }
+    }
+}
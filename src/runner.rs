@@ -0,0 +1,153 @@
+use crate::answer::Answer;
+use crate::cache::Cache;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::solution::Day;
+use crate::worker;
+use log::warn;
+use std::any::Any;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
+
+/// The path that reads the input from standard input.
+pub const STDIN: &str = "-";
+
+/// Set to a part number to make that part panic instead of being solved,
+/// so that tests can check how panics are reported without relying on a
+/// solver having a bug.
+pub const PANIC_PART: &str = "AOC24_PANIC_PART";
+
+/// The answer to one part of a puzzle and how long it took to solve.
+#[derive(Clone, Debug)]
+pub struct Part {
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// The results of running a day against one input.
+pub struct Report {
+    pub parse_time: Duration,
+    pub part1: Part,
+    pub part2: Part,
+}
+
+impl Report {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.part1.time + self.part2.time
+    }
+}
+
+/// Read the file at `path`, or standard input if `path` is `-`.
+pub fn read_input(path: &str) -> Result<String> {
+    if path == STDIN {
+        let mut contents = String::new();
+        return io::stdin()
+            .read_to_string(&mut contents)
+            .map(|_| contents)
+            .map_err(|e| Error::Io(display_path(path).to_string(), e));
+    }
+    fs::read_to_string(path).map_err(|e| Error::Io(path.to_string(), e))
+}
+
+/// How to refer to `path` in messages.
+pub fn display_path(path: &str) -> &str {
+    if path == STDIN {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+/// Parse `contents`, which were read from `path`, and time how long it took.
+pub fn parse(
+    day: &Day,
+    path: &str,
+    contents: &str,
+    params: &Params,
+) -> Result<(Box<dyn Any>, Duration)> {
+    let start = Instant::now();
+    let input = day
+        .parse(contents, params)
+        .map_err(|e| e.locate(display_path(path), contents))?;
+    Ok((input, start.elapsed()))
+}
+
+/// Solve one part of an input returned by `parse`.
+pub fn solve(day: &Day, input: &dyn Any, part: u32) -> Part {
+    if env::var(PANIC_PART).is_ok_and(|p| p == part.to_string()) {
+        panic!("{} is set to {}", PANIC_PART, part);
+    }
+    let start = Instant::now();
+    let answer = match part {
+        1 => day.part1(input),
+        2 => day.part2(input),
+        _ => unreachable!("Unknown part: {}", part),
+    };
+    Part {
+        answer,
+        time: start.elapsed(),
+    }
+}
+
+/// Read the input at `path` and solve both parts, with the parameters from
+/// its sidecar file.
+pub fn run(day: &Day, path: &str) -> Result<Report> {
+    let contents = read_input(path)?;
+    let (input, parse_time) = parse(day, path, &contents, &Params::for_input(path)?)?;
+    Ok(Report {
+        parse_time,
+        part1: solve(day, &*input, 1),
+        part2: solve(day, &*input, 2),
+    })
+}
+
+/// The parse time and each part of a run of one day.
+pub type Run = (Duration, Vec<(u32, Result<Part>)>);
+
+/// Read the input at `path`, parse it and solve `parts`. With a `timeout`,
+/// each part is solved in a worker process that is killed if it runs out
+/// of time. With a `cache`, parts that were solved before are taken from
+/// it, and the input is only parsed if one of them wasn't.
+pub fn run_parts(
+    day: &Day,
+    path: &str,
+    params: &Params,
+    parts: &[u32],
+    timeout: Option<Duration>,
+    cache: Option<&Cache>,
+) -> Result<Run> {
+    let contents = read_input(path)?;
+    let mut entry = cache
+        .map(|c| c.load(day, &contents, params))
+        .unwrap_or_default();
+    if let Some(parse_time) = entry.parse_time {
+        if parts.iter().all(|p| entry.parts.contains_key(p)) {
+            let parts = parts.iter().map(|p| (*p, Ok(entry.parts[p].clone())));
+            return Ok((parse_time, parts.collect()));
+        }
+    }
+
+    let (input, parse_time) = parse(day, path, &contents, params)?;
+    entry.parse_time = Some(parse_time);
+    let mut results = vec![];
+    for p in parts {
+        let part = match (entry.parts.get(p), timeout) {
+            (Some(part), _) => Ok(part.clone()),
+            (None, Some(timeout)) => worker::solve(day, &contents, params, *p, timeout),
+            (None, None) => Ok(solve(day, &*input, *p)),
+        };
+        if let Ok(part) = &part {
+            entry.parts.insert(*p, part.clone());
+        }
+        results.push((*p, part));
+    }
+    if let Some(cache) = cache {
+        // The answers are still good without the cache, so only warn.
+        if let Err(e) = cache.store(day, &contents, params, &entry) {
+            warn!("Not cached: {}", e);
+        }
+    }
+    Ok((parse_time, results))
+}
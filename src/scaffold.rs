@@ -0,0 +1,128 @@
+use crate::error::{Error, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = "\
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solution::Solution;
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Input = Vec<String>;
+    type Part1 = Answer;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part1(_lines: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_lines: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+";
+
+const TEST: &str = "
+#[test]
+fn dayNexample() {
+    assert_eq!(part1::<dayN::DayN>(\"dayNexample\"), Answer::Unsolved);
+    assert_eq!(part2::<dayN::DayN>(\"dayNexample\"), Answer::Unsolved);
+}
+";
+
+/// Start a new day in the source tree at `root`: a `src/days/dayN.rs` that
+/// solves nothing yet, registered in `src/days/mod.rs`, empty
+/// `data/dayN.txt` and `data/dayNexample.txt` inputs, and an example test
+/// in `tests/examples.rs`. Returns the files that were created or changed.
+pub fn new_day(root: &Path, number: u32) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&number) {
+        return Err(Error::Usage(format!("No such day: {}", number)));
+    }
+    let name = format!("day{}", number);
+    let source = root.join("src/days").join(format!("{}.rs", name));
+    if source.exists() {
+        return Err(Error::Usage(format!("{} already exists", source.display())));
+    }
+    let mod_rs = root.join("src/days/mod.rs");
+    let registered = register(&read(&mod_rs)?, number);
+    let examples = root.join("tests/examples.rs");
+    let tests = read(&examples)? + &TEST.replace("dayN", &name).replace("DayN", &title(number));
+
+    write(&source, &TEMPLATE.replace("DayN", &title(number)))?;
+    write(&mod_rs, &registered)?;
+    write(&examples, &tests)?;
+    let mut changed = vec![source, mod_rs, examples];
+    for input in [format!("{}.txt", name), format!("{}example.txt", name)] {
+        let path = root.join("data").join(input);
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+fn title(number: u32) -> String {
+    format!("Day{}", number)
+}
+
+// Add the day to the `pub mod` declarations and the list of days in
+// `mod.rs`.
+fn register(mod_rs: &str, number: u32) -> String {
+    let mut lines: Vec<String> = mod_rs.lines().map(|l| l.to_string()).collect();
+    let declaration = format!("pub mod day{};", number);
+    insert(&mut lines, &declaration, "pub mod day", |l| {
+        l.trim().to_string()
+    });
+    let entry = format!("Day::new::<day{0}::{1}>({0}),", number, title(number));
+    insert(&mut lines, &entry, "Day::new::<day", day_number);
+    lines.join("\n") + "\n"
+}
+
+// Put `line` among the lines that start with `prefix`, before the first one
+// that sorts after it by `key`, or uncomment it if it is there but commented
+// out.
+fn insert<K: Ord>(lines: &mut Vec<String>, line: &str, prefix: &str, key: impl Fn(&str) -> K) {
+    let indent = |l: &str| l[..l.len() - l.trim_start().len()].to_string();
+    if let Some(l) = lines
+        .iter_mut()
+        .find(|l| l.trim().trim_start_matches('/').trim() == line)
+    {
+        *l = indent(l) + line;
+        return;
+    }
+    let similar: Vec<usize> = (0..lines.len())
+        .filter(|i| lines[*i].trim().starts_with(prefix))
+        .collect();
+    let at = similar
+        .iter()
+        .find(|i| key(&lines[**i]) > key(line))
+        .copied()
+        .or(similar.last().map(|i| i + 1))
+        .unwrap_or(lines.len());
+    let indent = similar
+        .first()
+        .map_or(String::new(), |i| indent(&lines[*i]));
+    lines.insert(at, indent + line);
+}
+
+// The number after the first `day` in a line.
+fn day_number(line: &str) -> Option<u32> {
+    let (_, rest) = line.split_once("day")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| Error::Io(path.display().to_string(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|e| Error::Io(path.display().to_string(), e))
+}
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The result of a search: the cost of the cheapest path to every node that
+/// was reached, and enough about those paths to rebuild them.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    costs: HashMap<N, C>,
+    // Every node that comes just before each node on one of its cheapest
+    // paths. The start has none.
+    predecessors: HashMap<N, Vec<N>>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            costs: HashMap::from([(start.clone(), zero)]),
+            predecessors: HashMap::from([(start, vec![])]),
+            goal: None,
+        }
+    }
+
+    /// The cost of the cheapest path to `node`, if it was reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The cost of every node that was reached.
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// The goal that stopped the search, if one was reached.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// One of the cheapest paths from the start to `node`, including both.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut preds = self.predecessors.get(node)?;
+        while let Some(p) = preds.first() {
+            path.push(p.clone());
+            preds = &self.predecessors[p];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every node on any of the cheapest paths from the start to any of
+    /// `targets`.
+    pub fn on_paths(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut todo: Vec<N> = targets
+            .into_iter()
+            .filter(|t| self.predecessors.contains_key(t))
+            .collect();
+        while let Some(n) = todo.pop() {
+            if seen.insert(n.clone()) {
+                todo.extend(self.predecessors[&n].iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search of everything reachable from `start`, where every
+/// step costs 1.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let cost = search.costs[&node] + 1;
+        for next in neighbors(&node) {
+            match search.costs.get(&next) {
+                None => {
+                    search.costs.insert(next.clone(), cost);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back(next);
+                }
+                Some(c) if *c == cost => search
+                    .predecessors
+                    .get_mut(&next)
+                    .unwrap()
+                    .push(node.clone()),
+                Some(_) => {}
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's search from `start`, where `neighbors` gives each node that
+/// can be reached from a node and the cost of getting there.
+///
+/// The search stops once every path as cheap as the first path to a node
+/// for which `goal` is true has been found, so that `on_paths` sees all of
+/// the cheapest paths to every equally cheap goal. Use `|_| false` to
+/// search everything reachable.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), goal)
+}
+
+/// A* search, which is Dijkstra's search guided by a `heuristic` estimate
+/// of the remaining cost to a goal. The heuristic must never overestimate
+/// for the result to be the cheapest path.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+    let mut goal_cost = None;
+
+    while let Some(Entry {
+        priority,
+        cost,
+        node,
+    }) = heap.pop()
+    {
+        if goal_cost.is_some_and(|g| priority > g) {
+            break;
+        }
+        if cost > search.costs[&node] {
+            continue; // already reached more cheaply
+        }
+        if goal(&node) {
+            goal_cost.get_or_insert(cost);
+            search.goal.get_or_insert(node.clone());
+            continue;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            match search.costs.get(&next) {
+                Some(c) if *c < next_cost => {}
+                Some(c) if *c == next_cost => {
+                    let preds = search.predecessors.get_mut(&next).unwrap();
+                    if !preds.contains(&node) {
+                        preds.push(node.clone());
+                    }
+                }
+                _ => {
+                    search.costs.insert(next.clone(), next_cost);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    heap.push(Entry {
+                        priority: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+    search
+}
+
+// A heap entry ordered so that `BinaryHeap` pops the lowest priority first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
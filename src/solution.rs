@@ -0,0 +1,71 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::params::Params;
+use std::any::Any;
+
+/// A single day's puzzle.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input: 'static;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Parse `input` with explicit puzzle parameters. Only days whose puzzle
+    /// depends on something other than the input need to override this.
+    fn parse_with(input: &str, _params: &Params) -> Result<Self::Input> {
+        Self::parse(input)
+    }
+
+    /// Parse `input` and solve part 1.
+    fn solve_part1(input: &str) -> Result<Answer> {
+        Ok(Self::part1(&Self::parse(input)?).into())
+    }
+
+    /// Parse `input` and solve part 2.
+    fn solve_part2(input: &str) -> Result<Answer> {
+        Ok(Self::part2(&Self::parse(input)?).into())
+    }
+}
+
+/// A registered day with its `Solution` type erased, so that all of the
+/// days can be stored together and looked up by name.
+pub struct Day {
+    pub number: u32,
+    parse: fn(&str, &Params) -> Result<Box<dyn Any>>,
+    part1: fn(&dyn Any) -> Answer,
+    part2: fn(&dyn Any) -> Answer,
+}
+
+impl Day {
+    pub fn new<S: Solution>(number: u32) -> Self {
+        Self {
+            number,
+            parse: |s, p| Ok(Box::new(S::parse_with(s, p)?)),
+            part1: |i| S::part1(i.downcast_ref().unwrap()).into(),
+            part2: |i| S::part2(i.downcast_ref().unwrap()).into(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("day{}", self.number)
+    }
+
+    pub fn parse(&self, input: &str, params: &Params) -> Result<Box<dyn Any>> {
+        (self.parse)(input, params)
+    }
+
+    /// Solve part 1 of an input returned by `parse`.
+    pub fn part1(&self, input: &dyn Any) -> Answer {
+        (self.part1)(input)
+    }
+
+    /// Solve part 2 of an input returned by `parse`.
+    pub fn part2(&self, input: &dyn Any) -> Answer {
+        (self.part2)(input)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::answer::Answer;
+use crate::cache::Cache;
+use crate::days;
+use crate::error::{Error, Result};
+use crate::params::Params;
+use crate::runner;
+use crate::solution::Day;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The expected answers for one input file. A part without an answer
+/// (e.g. part 2 of an example that only covers part 1) is not checked.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn part(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Expected answers keyed by input file name without the `.txt`, e.g.
+/// `day12example3`.
+pub type Manifest = BTreeMap<String, Expected>;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The input could not be read or parsed.
+    Error(String),
+}
+
+/// Read a manifest of tables like:
+///
+/// ```toml
+/// [day17example2]
+/// part1 = "5,7,3,0"
+/// part2 = 117440
+/// ```
+pub fn read_manifest(path: &str) -> Result<Manifest> {
+    let contents = runner::read_input(path)?;
+    let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| {
+        let at = e.span().map_or(0, |s| s.start);
+        Error::parse(&contents[at..], e.message()).locate(path, &contents)
+    })?;
+
+    let mut manifest = Manifest::new();
+    for (name, parts) in table {
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| manifest_error(path, &name, "expected a table of answers"))?;
+        let mut expected = Expected::default();
+        for (part, value) in parts {
+            let answer = match value {
+                toml::Value::Integer(v) => Answer::Int(*v),
+                toml::Value::String(s) => Answer::Text(s.clone()),
+                _ => {
+                    return Err(manifest_error(
+                        path,
+                        &name,
+                        "answers must be integers or strings",
+                    ))
+                }
+            };
+            match part.as_str() {
+                "part1" => expected.part1 = Some(answer),
+                "part2" => expected.part2 = Some(answer),
+                _ => {
+                    return Err(manifest_error(
+                        path,
+                        &name,
+                        &format!("unknown part `{}`", part),
+                    ))
+                }
+            }
+        }
+        manifest.insert(name, expected);
+    }
+    Ok(manifest)
+}
+
+fn manifest_error(path: &str, name: &str, message: &str) -> Error {
+    Error::Usage(format!("{}: [{}]: {}", path, name, message))
+}
+
+/// The day that solves an input file name, e.g. `day12example3` is day 12.
+pub fn day_for(name: &str) -> Option<&'static Day> {
+    let digits = name
+        .strip_prefix("day")?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    days::find(&format!("day{}", digits))
+}
+
+/// Solve the parts of `dir/name.txt` that have an expected answer and
+/// compare the results.
+pub fn verify(
+    dir: &Path,
+    name: &str,
+    expected: &Expected,
+    cache: Option<&Cache>,
+) -> Vec<(u32, Outcome)> {
+    let parts: Vec<u32> = (1..=2).filter(|p| expected.part(*p).is_some()).collect();
+    let path = dir.join(format!("{}.txt", name));
+    let path = path.to_string_lossy();
+    let run = day_for(name)
+        .ok_or_else(|| Error::Usage(format!("No day solves {}", name)))
+        .and_then(|day| {
+            let params = Params::for_input(&path)?;
+            runner::run_parts(day, &path, &params, &parts, None, cache)
+        });
+    let solved = match run {
+        Ok((_, solved)) => solved,
+        Err(e) => {
+            return parts
+                .into_iter()
+                .map(|p| (p, Outcome::Error(e.to_string())))
+                .collect()
+        }
+    };
+
+    solved
+        .into_iter()
+        .map(|(p, part)| {
+            let expected = expected.part(p).unwrap().clone();
+            let actual = part.map(|part| part.answer);
+            let outcome = match actual {
+                Ok(actual) if actual == expected => Outcome::Pass,
+                Ok(actual) => Outcome::Fail { expected, actual },
+                Err(e) => Outcome::Error(e.to_string()),
+            };
+            (p, outcome)
+        })
+        .collect()
+}
@@ -0,0 +1,103 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parallel;
+use crate::params::Params;
+use crate::runner::{self, Part};
+use crate::solution::Day;
+use log::debug;
+use serde_json::Value;
+use std::env;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What the message of a panic is reported after, as `Error: panicked: ...`.
+pub const PANICKED: &str = "panicked: ";
+
+// How often to check whether a worker has finished.
+const POLL: Duration = Duration::from_millis(5);
+
+/// Solve one part of `contents` in a worker process, a copy of this program
+/// run as `dayN - --part N --format json` with the same parameters and
+/// `--jobs`, and kill it if it takes longer than `timeout`. Unlike a thread,
+/// a process can be stopped in the middle of a solver that never checks
+/// whether it should stop.
+pub fn solve(
+    day: &Day,
+    contents: &str,
+    params: &Params,
+    part: u32,
+    timeout: Duration,
+) -> Result<Part> {
+    let exe = env::current_exe().map_err(|e| Error::Io("current executable".to_string(), e))?;
+    let jobs = parallel::jobs().map(|n| ["--jobs".to_string(), n.to_string()]);
+    let mut command = Command::new(exe);
+    command
+        .arg(day.name())
+        .arg(runner::STDIN)
+        .args(["--part", &part.to_string(), "--format", "json"])
+        .args(params.to_args())
+        .args(jobs.into_iter().flatten())
+        .env_remove("RUST_LOG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    debug!("Starting worker {:?}", command);
+    let mut child = command.spawn().map_err(|e| Error::Io(day.name(), e))?;
+    // Dropping stdin closes it, so the worker sees the end of the input.
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(contents.as_bytes())
+        .map_err(|e| Error::Io(day.name(), e))?;
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| Error::Io(day.name(), e))? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Timeout(timeout));
+        }
+        thread::sleep(POLL);
+    };
+
+    let (stdout, stderr) = (read_all(child.stdout.take()), read_all(child.stderr.take()));
+    if !status.success() {
+        let panic = format!("Error: {}", PANICKED);
+        let message = match stderr.lines().find_map(|l| l.strip_prefix(&panic)) {
+            Some(message) => format!("part {} panicked: {}", part, message),
+            None => format!("part {} failed: {}", part, stderr.trim()),
+        };
+        return Err(Error::Worker(message));
+    }
+    part_from_json(&stdout)
+        .ok_or_else(|| Error::Worker(format!("part {}: unexpected output: {}", part, stdout)))
+}
+
+fn read_all(pipe: Option<impl Read>) -> String {
+    let mut output = String::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_string(&mut output);
+    }
+    output
+}
+
+// The only part in the JSON a worker prints for a run.
+fn part_from_json(output: &str) -> Option<Part> {
+    let run: Value = serde_json::from_str(output.trim()).ok()?;
+    let part = run["parts"].get(0)?;
+    let answer = match &part["answer"] {
+        Value::Null => Answer::Unsolved,
+        Value::String(s) => Answer::Text(s.clone()),
+        v => Answer::Int(v.as_i64()?),
+    };
+    Some(Part {
+        answer,
+        time: Duration::from_nanos(part["time_ns"].as_u64()?),
+    })
+}
@@ -0,0 +1,8 @@
+use aoc24::answer::Answer;
+
+#[test]
+fn numbers_too_big_for_an_int_are_text() {
+    assert_eq!(Answer::from(u64::MAX), Answer::from("18446744073709551615"));
+    assert_eq!(Answer::from(i64::MAX as u64), Answer::Int(i64::MAX));
+    assert_eq!(Answer::from(-1), Answer::Int(-1));
+}
@@ -0,0 +1,68 @@
+use aoc24::runner::PANIC_PART;
+use std::process::Command;
+
+// Run a batch with the aoc24 binary, since each part is solved in a worker
+// process that runs a copy of it. Returns whether it succeeded and what it
+// printed.
+fn batch(args: &[&str]) -> (bool, String) {
+    batch_with(Command::new(env!("CARGO_BIN_EXE_aoc24")), args)
+}
+
+fn batch_with(mut command: Command, args: &[&str]) -> (bool, String) {
+    let output = command
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("batch")
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn panics_and_errors_are_flagged() {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_aoc24"));
+    command.env(PANIC_PART, "2");
+    let (ok, stdout) = batch_with(
+        command,
+        &["day5", "data/day5example.txt", "data/day5missing.txt"],
+    );
+    assert!(!ok);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[2].contains(" 143 "));
+    assert!(lines[2].contains("Error: part 2 panicked: AOC24_PANIC_PART is set to 2"));
+    assert!(lines[2].ends_with("failed"));
+    assert!(lines[3].contains("Error: data/day5missing.txt: "));
+    assert!(stdout.contains("2 inputs, 2 flagged\n"));
+}
+
+#[test]
+fn inputs_that_never_finish_time_out() {
+    let (ok, stdout) = batch(&[
+        "day17",
+        "tests/fixtures/day17-forever.txt",
+        "data/day17example.txt",
+        "--budget",
+        "0.5",
+    ]);
+    assert!(!ok);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[2].contains(" TIMEOUT "));
+    assert!(lines[3].contains(" 4,6,3,5,6,3,5,2,1,0 "));
+    assert!(stdout.contains("2 inputs, 1 flagged\n"));
+}
+
+#[test]
+fn solved_inputs_are_flagged_over_budget() {
+    let paths = ["data/day12example.txt", "data/day12example2.txt"];
+    let (ok, stdout) = batch(&[&["day12"], &paths[..]].concat());
+    assert!(ok);
+    assert!(stdout.lines().nth(3).unwrap().contains(" 772 "));
+    assert!(stdout.contains("2 inputs, 0 flagged\n"));
+
+    let (ok, stdout) = batch(&[&["day12"], &paths[..], &["--budget", "0"]].concat());
+    assert!(!ok);
+    assert!(stdout.contains("2 inputs, 2 flagged\n"));
+}
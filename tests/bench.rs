@@ -0,0 +1,48 @@
+use std::fs;
+use std::process::Command;
+
+fn bench(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc24"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["bench", "--iterations", "1"])
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn baselines_are_only_compared_on_the_same_input() {
+    let csv = std::env::temp_dir().join(format!("aoc24-bench-{}.csv", std::process::id()));
+    let csv = csv.to_str().unwrap();
+    let _ = fs::remove_file(csv);
+
+    let (ok, _, _) = bench(&["day12", "data/day12example.txt", "--save", csv]);
+    assert!(ok);
+    let saved = fs::read_to_string(csv).unwrap();
+    assert!(saved.starts_with("day,input,step,"));
+    assert!(saved.contains("\nday12,data/day12example.txt,part1,"));
+
+    let (ok, stdout, _) = bench(&["day12", "data/day12example.txt", "--compare", csv]);
+    assert!(ok);
+    assert!(stdout.contains("Baseline"));
+
+    let (ok, stdout, stderr) = bench(&["day12", "data/day12example2.txt", "--compare", csv]);
+    assert!(!ok);
+    assert!(!stdout.contains("Benchmarking"));
+    assert!(stderr.contains("no timings of day12 with data/day12example2.txt"));
+
+    fs::remove_file(csv).unwrap();
+}
+
+#[test]
+fn json_output_is_rejected() {
+    let (ok, stdout, stderr) = bench(&["day12", "data/day12example.txt", "--format", "json"]);
+    assert!(!ok);
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("--format json only works with dayN and all"));
+}
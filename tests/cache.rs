@@ -0,0 +1,90 @@
+use aoc24::answer::Answer;
+use aoc24::cache::{self, Cache};
+use aoc24::days;
+use aoc24::params::Params;
+use aoc24::runner::{self, Part};
+use std::fs;
+use std::time::Duration;
+
+fn cache_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc24-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn answers(run: &runner::Run) -> Vec<Answer> {
+    run.1
+        .iter()
+        .map(|(_, p)| p.as_ref().unwrap().answer.clone())
+        .collect()
+}
+
+#[test]
+fn solved_parts_are_cached() {
+    let dir = cache_dir("cache-solved");
+    let cache = Cache::new(&dir);
+    let day = days::find("day7").unwrap();
+    let path = format!("{}/data/day7example.txt", env!("CARGO_MANIFEST_DIR"));
+    let params = Params::default();
+
+    let run = runner::run_parts(day, &path, &params, &[1, 2], None, Some(&cache)).unwrap();
+    assert_eq!(answers(&run), [Answer::Int(3749), Answer::Int(11387)]);
+    let contents = runner::read_input(&path).unwrap();
+    let entry = cache.load(day, &contents, &params);
+    assert_eq!(entry.parts[&2].answer, Answer::Int(11387));
+
+    // Whatever is in the cache is returned without solving again.
+    let mut entry = entry;
+    entry.parts.insert(
+        1,
+        Part {
+            answer: Answer::from("cached"),
+            time: Duration::ZERO,
+        },
+    );
+    cache.store(day, &contents, &params, &entry).unwrap();
+    let run = runner::run_parts(day, &path, &params, &[1], None, Some(&cache)).unwrap();
+    assert_eq!(answers(&run), [Answer::from("cached")]);
+    let run = runner::run_parts(day, &path, &params, &[1], None, None).unwrap();
+    assert_eq!(answers(&run), [Answer::Int(3749)]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn entries_are_keyed_by_input_and_params() {
+    let dir = cache_dir("cache-keys");
+    let cache = Cache::new(&dir);
+    let day = days::find("day20").unwrap();
+    let mut params = Params::default();
+    params.set("min-save", 1);
+    cache
+        .store(
+            day,
+            "#S.E#",
+            &params,
+            &cache::Entry {
+                parse_time: Some(Duration::ZERO),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert!(cache.load(day, "#S.E#", &params).parse_time.is_some());
+    assert!(cache.load(day, "#S..E#", &params).parse_time.is_none());
+    assert!(cache
+        .load(day, "#S.E#", &Params::default())
+        .parse_time
+        .is_none());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn every_day_has_its_own_version() {
+    let versions: Vec<String> = days::all().iter().map(cache::version).collect();
+    let mut unique = versions.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), versions.len());
+    assert_eq!(cache::version(days::find("day7").unwrap()), versions[6]);
+}
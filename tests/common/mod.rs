@@ -0,0 +1,19 @@
+use aoc24::answer::Answer;
+use aoc24::params::Params;
+use aoc24::runner;
+use aoc24::solution::Solution;
+
+// Parse an example with the parameters from its sidecar file.
+pub fn input<S: Solution>(name: &str) -> S::Input {
+    let path = format!("{}/data/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    let contents = runner::read_input(&path).unwrap();
+    S::parse_with(&contents, &Params::for_input(&path).unwrap()).unwrap()
+}
+
+pub fn part1<S: Solution>(name: &str) -> Answer {
+    S::part1(&input::<S>(name)).into()
+}
+
+pub fn part2<S: Solution>(name: &str) -> Answer {
+    S::part2(&input::<S>(name)).into()
+}
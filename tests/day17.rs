@@ -0,0 +1,319 @@
+use aoc24::days::day17::{self, Computer, Day17, Debugger, Stop};
+use aoc24::solution::Solution;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn program(s: &str) -> Vec<usize> {
+    s.split(',').map(|n| n.parse().unwrap()).collect()
+}
+
+fn parse_error(input: &str) -> String {
+    input
+        .parse::<Computer>()
+        .unwrap_err()
+        .locate("day17.txt", input)
+        .to_string()
+}
+
+#[test]
+fn programs_that_cannot_run_are_rejected() {
+    let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,7\n";
+    assert_eq!(
+        parse_error(input),
+        "day17.txt:5:16: combo operand 7 is reserved"
+    );
+    let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5\n";
+    assert_eq!(
+        parse_error(input),
+        "day17.txt:5:10: program has an opcode without an operand"
+    );
+    // a literal 7 is fine
+    let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,5\n";
+    assert!(input.parse::<Computer>().is_ok());
+}
+
+#[test]
+fn jumping_to_the_last_number_halts() {
+    let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,1,5,4\n";
+    assert_eq!(Day17::part1(&input.parse().unwrap()), "");
+}
+
+#[test]
+fn dividing_by_huge_powers_of_two_gives_zero() {
+    let output = |source: &str| Day17::part1(&day17::assemble(source).unwrap());
+    assert_eq!(output(".A 100\nadv A\nout A\n"), "0");
+    // 5 * 2^32 + 31, which is 31 when cut down to a u32
+    assert_eq!(output(".A 21474836511\nbdv A\nout B\n"), "0");
+    assert_eq!(output(".A 64\ncdv A\nout C\n"), "0");
+
+    let mut debugger = Debugger::new(day17::assemble(".A 100\nadv A\nout A\n").unwrap());
+    assert_eq!(debugger.step(1), Stop::Stepped);
+    assert_eq!(debugger.computer().registers()[0], 0);
+}
+
+#[test]
+fn disassemble_resolves_operands_and_labels() {
+    let listing = day17::disassemble(&program("2,4,1,1,7,5,1,5,4,1,5,5,0,3,3,0"));
+    assert_eq!(
+        listing,
+        "L0:\n     0  bst A\n     2  bxl 1\n     4  cdv B\n     6  bxl 5\n     \
+         8  bxc 1\n    10  out B\n    12  adv 3\n    14  jnz L0\n"
+    );
+}
+
+#[test]
+fn disassemble_keeps_unusual_jumps_and_operands() {
+    let listing = day17::disassemble(&program("3,5,5,7,3,1,0,2,3,6"));
+    assert_eq!(
+        listing,
+        "     0  jnz 5\n     2  out 7  ; reserved\n     4  jnz 1\nL6:\n     \
+         6  adv 2\n     8  jnz L6\n"
+    );
+}
+
+#[test]
+fn disassemble_labels_the_end_of_the_program() {
+    let computer: Computer = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 3,4,5,4\n"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        day17::disassemble(computer.program()),
+        "     0  jnz L4\n     2  out A\nL4:\n"
+    );
+}
+
+#[test]
+fn assemble_resolves_labels_and_registers() {
+    let source = ".A 2024\nloop:\n    adv 3\n    out A  ; print\n    jnz loop\n";
+    assert_eq!(
+        day17::assemble(source).unwrap().to_string(),
+        "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0\n"
+    );
+}
+
+#[test]
+fn assemble_reads_back_a_listing() {
+    let input = "Register A: 17323786\nRegister B: 0\nRegister C: 0\n\n\
+                 Program: 2,4,1,1,7,5,1,5,4,1,5,5,0,3,3,0\n";
+    let computer: Computer = input.parse().unwrap();
+    let listing = format!(".A 17323786\n{}", day17::disassemble(computer.program()));
+    assert_eq!(day17::assemble(&listing).unwrap(), computer);
+}
+
+#[test]
+fn assemble_reports_where_the_source_is_wrong() {
+    let error = |source: &str| {
+        day17::assemble(source)
+            .unwrap_err()
+            .locate("test.s", source)
+            .to_string()
+    };
+    assert_eq!(
+        error("adv 3\nout D\n"),
+        "test.s:2:5: `D` is not a 3-bit operand"
+    );
+    assert_eq!(
+        error("adv 3\njnz end\n"),
+        "test.s:2:5: undefined label `end`"
+    );
+    assert_eq!(
+        error("adv 3\nadv 3\nadv 3\nadv 3\nend: jnz end\n"),
+        "test.s:5:10: `end` is at 8, too far to jump to"
+    );
+    assert_eq!(
+        error("0 adv 3\n4 out A\n"),
+        "test.s:2:1: expected address 2"
+    );
+    assert_eq!(error("mul 3\n"), "test.s:1:1: unknown instruction `mul`");
+    assert_eq!(error("out\n"), "test.s:1:4: missing operand");
+    assert_eq!(error("out 7\n"), "test.s:1:5: combo operand 7 is reserved");
+}
+
+fn debugger() -> Debugger {
+    let input = "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0\n";
+    Debugger::new(input.parse().unwrap())
+}
+
+#[test]
+fn debugger_stops_at_breakpoints_and_watched_changes() {
+    let mut debugger = debugger();
+    debugger.set_breakpoint(4, true);
+    assert_eq!(debugger.run(), Stop::Breakpoint(4));
+    assert_eq!(debugger.computer().output(), [5]);
+
+    debugger.set_breakpoint(4, false);
+    debugger.set_watch(0, true);
+    assert_eq!(
+        debugger.run(),
+        Stop::Watch {
+            register: 'A',
+            from: 253,
+            to: 31
+        }
+    );
+    debugger.set_watch(0, false);
+    assert_eq!(debugger.run(), Stop::Halted);
+    assert_eq!(debugger.computer().output(), [5, 7, 3, 0]);
+}
+
+#[test]
+fn debugger_steps_back_through_history() {
+    let mut debugger = debugger();
+    let start = debugger.computer().clone();
+    assert_eq!(debugger.step(5), Stop::Stepped);
+    assert_eq!(debugger.computer().ip(), 4);
+    assert_eq!(debugger.computer().registers(), [31, 0, 0]);
+    assert_eq!(debugger.back(2), Stop::Stepped);
+    assert_eq!(debugger.computer().ip(), 0);
+    assert_eq!(debugger.back(10), Stop::Start);
+    assert_eq!(debugger.computer(), &start);
+}
+
+#[test]
+fn debugger_gives_up_on_programs_that_never_halt() {
+    let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,3,0\n";
+    let mut debugger = Debugger::new(input.parse().unwrap());
+    debugger.set_limit(1000);
+    assert_eq!(debugger.run(), Stop::Limit(1000));
+    assert_eq!(debugger.computer().output().len(), 500);
+    assert_eq!(debugger.back(1000), Stop::Stepped);
+    assert_eq!(debugger.computer().output().len(), 0);
+    assert_eq!(
+        debugger.command("c").unwrap(),
+        "still running after 1000 steps\nA 1  B 0  C 0  ip 0: out A"
+    );
+}
+
+#[test]
+fn debug_sessions_start_without_a_banner() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc24"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["day17", "debug", "data/day17example2.txt"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"s\nq\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("A 2024  B 0  C 0  ip 0: adv 3\n"));
+    assert!(stdout.contains("(day17) A 253  B 0  C 0  ip 2: out A\n"));
+}
+
+#[test]
+fn debugger_commands_describe_the_state() {
+    let mut debugger = debugger();
+    assert_eq!(
+        debugger.command("step 2").unwrap(),
+        "A 253  B 0  C 0  ip 4: jnz L0"
+    );
+    assert_eq!(debugger.command("output").unwrap(), "5");
+    assert_eq!(
+        debugger.command("list").unwrap(),
+        "   L0:\n        0  adv 3\n        2  out A\n>       4  jnz L0"
+    );
+    assert_eq!(debugger.command("b 2").unwrap(), "breakpoint at 2 set");
+    assert_eq!(
+        debugger.command("c").unwrap(),
+        "breakpoint at 2\nA 31  B 0  C 0  ip 2: out A"
+    );
+    assert!(debugger.command("watch D").is_err());
+}
+
+#[test]
+fn quine_builds_a_from_the_end_of_the_program() {
+    let source = ".A 2024\nadv 3\nout A\njnz 0\n";
+    assert_eq!(
+        day17::quine(&day17::assemble(source).unwrap()),
+        Some(117440)
+    );
+    // the same loop written differently still needs the search to work
+    let source = "bst A\nbxl 5\ncdv B\nadv 3\nbxc\nbxl 6\nout B\njnz 0\n";
+    let computer = day17::assemble(source).unwrap();
+    let a = day17::quine(&computer).unwrap();
+    let input = computer.to_string().replacen("0", &a.to_string(), 1);
+    let quine: Computer = input.parse().unwrap();
+    let program: Vec<String> = computer.program().iter().map(|v| v.to_string()).collect();
+    assert_eq!(Day17::part1(&quine), program.join(","));
+}
+
+#[test]
+fn quine_reports_when_there_is_no_answer() {
+    // always outputs 1, which isn't the program
+    let computer = day17::assemble("adv 3\nout 1\njnz 0\n").unwrap();
+    assert!(day17::is_digit_loop(computer.program()));
+    assert_eq!(day17::quine(&computer), None);
+    assert_eq!(Day17::part2(&computer), None);
+    // shifts one bit at a time
+    let computer = day17::assemble("adv 1\nout A\njnz 0\n").unwrap();
+    assert!(!day17::is_digit_loop(computer.program()));
+    assert_eq!(day17::quine(&computer), None);
+    assert_eq!(Day17::part2(&computer), None);
+}
+
+#[test]
+fn quine_needs_outputs_that_only_depend_on_a() {
+    // B flips every time around, so each output also depends on how many
+    // came before it
+    let computer = day17::assemble("bxl 1\nadv 3\nout B\njnz 0\n").unwrap();
+    assert!(!day17::is_digit_loop(computer.program()));
+    assert_eq!(day17::quine(&computer), None);
+    // but a loop that sets B from A before reading it is fine
+    let computer = day17::assemble("bst A\nbxl 1\nadv 3\nout B\njnz 0\n").unwrap();
+    assert!(day17::is_digit_loop(computer.program()));
+}
+
+#[test]
+fn decompile_gives_each_output_as_an_expression_of_a() {
+    let program = program("2,4,1,1,7,5,1,5,4,1,5,5,0,3,3,0");
+    let iteration = day17::decompile(&program).unwrap();
+    assert_eq!(
+        iteration.to_string(),
+        "loop {\n    out(((A % 8) ^ 4 ^ (A >> ((A % 8) ^ 1))) % 8);\n    A >>= 3;\n    \
+         if A == 0 {\n        break;\n    }\n}\n"
+    );
+
+    // running the expressions gives the same output as running the program
+    for a in (1..5000).chain([17323786, 164278764924605]) {
+        let input = format!("Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,1,7,5,1,5,4,1,5,5,0,3,3,0\n", a);
+        let computer: Computer = input.parse().unwrap();
+        let mut registers = [a, 0, 0];
+        let mut output = vec![];
+        loop {
+            output.extend(
+                iteration
+                    .outputs
+                    .iter()
+                    .map(|e| e.eval(registers).to_string()),
+            );
+            registers = iteration.registers.clone().map(|e| e.eval(registers));
+            if registers[0] == 0 {
+                break;
+            }
+        }
+        assert_eq!(Day17::part1(&computer), output.join(","), "A = {}", a);
+    }
+}
+
+#[test]
+fn decompile_carries_registers_the_next_iteration_reads() {
+    let computer = day17::assemble("bxl 3\nbxc\ncdv 1\nout B\nadv 1\njnz 0\n").unwrap();
+    assert_eq!(
+        day17::decompile(computer.program()).unwrap().to_string(),
+        "loop {\n    out((B ^ 3 ^ C) % 8);\n    (A, B, C) = (A >> 1, B ^ 3 ^ C, A >> 1);\n    \
+         if A == 0 {\n        break;\n    }\n}\n"
+    );
+    let computer = day17::assemble("bst A\nbdv 2\nout B\n").unwrap();
+    assert_eq!(
+        day17::decompile(computer.program()).unwrap().to_string(),
+        "out((A >> 2) % 8);\nB = A >> 2;\n"
+    );
+}
+
+#[test]
+fn decompile_needs_a_single_loop() {
+    assert_eq!(day17::decompile(&program("3,4,5,4,3,0")), None);
+    assert_eq!(day17::decompile(&program("0,3,5,4,3,2")), None);
+    assert_eq!(day17::decompile(&program("5,7,3,0")), None);
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use aoc24::answer::Answer;
+use aoc24::days::*;
+use common::{part1, part2};
+
+#[test]
+fn day1example() {
+    assert_eq!(part1::<day1::Day1>("day1example"), Answer::Int(11));
+    assert_eq!(part2::<day1::Day1>("day1example"), Answer::Int(31));
+}
+
+#[test]
+fn day2example() {
+    assert_eq!(part1::<day2::Day2>("day2example"), Answer::Int(2));
+    assert_eq!(part2::<day2::Day2>("day2example"), Answer::Int(4));
+}
+
+#[test]
+fn day3example() {
+    assert_eq!(part1::<day3::Day3>("day3example"), Answer::Int(161));
+}
+
+#[test]
+fn day3example2() {
+    assert_eq!(part2::<day3::Day3>("day3example2"), Answer::Int(48));
+}
+
+#[test]
+fn day4example() {
+    assert_eq!(part1::<day4::Day4>("day4example"), Answer::Int(18));
+    assert_eq!(part2::<day4::Day4>("day4example"), Answer::Int(9));
+}
+
+#[test]
+fn day5example() {
+    assert_eq!(part1::<day5::Day5>("day5example"), Answer::Int(143));
+    assert_eq!(part2::<day5::Day5>("day5example"), Answer::Int(123));
+}
+
+#[test]
+fn day6example() {
+    assert_eq!(part1::<day6::Day6>("day6example"), Answer::Int(41));
+    assert_eq!(part2::<day6::Day6>("day6example"), Answer::Int(6));
+}
+
+#[test]
+fn day7example() {
+    assert_eq!(part1::<day7::Day7>("day7example"), Answer::Int(3749));
+    assert_eq!(part2::<day7::Day7>("day7example"), Answer::Int(11387));
+}
+
+#[test]
+fn day8example() {
+    assert_eq!(part1::<day8::Day8>("day8example"), Answer::Int(14));
+    assert_eq!(part2::<day8::Day8>("day8example"), Answer::Int(34));
+}
+
+#[test]
+fn day9example() {
+    assert_eq!(part1::<day9::Day9>("day9example"), Answer::Int(1928));
+    assert_eq!(part2::<day9::Day9>("day9example"), Answer::Int(2858));
+}
+
+#[test]
+fn day10example() {
+    assert_eq!(part1::<day10::Day10>("day10example"), Answer::Int(36));
+    assert_eq!(part2::<day10::Day10>("day10example"), Answer::Int(81));
+}
+
+#[test]
+fn day11example() {
+    assert_eq!(part1::<day11::Day11>("day11example"), Answer::Int(55312));
+}
+
+#[test]
+fn day12example() {
+    assert_eq!(part1::<day12::Day12>("day12example"), Answer::Int(140));
+    assert_eq!(part2::<day12::Day12>("day12example"), Answer::Int(80));
+}
+
+#[test]
+fn day12example2() {
+    assert_eq!(part1::<day12::Day12>("day12example2"), Answer::Int(772));
+    assert_eq!(part2::<day12::Day12>("day12example2"), Answer::Int(436));
+}
+
+#[test]
+fn day12example3() {
+    assert_eq!(part1::<day12::Day12>("day12example3"), Answer::Int(1930));
+    assert_eq!(part2::<day12::Day12>("day12example3"), Answer::Int(1206));
+}
+
+#[test]
+fn day12example4() {
+    assert_eq!(part2::<day12::Day12>("day12example4"), Answer::Int(236));
+}
+
+#[test]
+fn day12example5() {
+    assert_eq!(part2::<day12::Day12>("day12example5"), Answer::Int(368));
+}
+
+#[test]
+fn day13example() {
+    assert_eq!(part1::<day13::Day13>("day13example"), Answer::Int(480));
+}
+
+#[test]
+fn day14example() {
+    assert_eq!(part1::<day14::Day14>("day14example"), Answer::Int(12));
+}
+
+#[test]
+fn day15example() {
+    assert_eq!(part1::<day15::Day15>("day15example"), Answer::Int(10092));
+    assert_eq!(part2::<day15::Day15>("day15example"), Answer::Int(9021));
+}
+
+#[test]
+fn day15example1() {
+    assert_eq!(part1::<day15::Day15>("day15example1"), Answer::Int(2028));
+}
+
+#[test]
+fn day16example() {
+    assert_eq!(part1::<day16::Day16>("day16example"), Answer::Int(7036));
+    assert_eq!(part2::<day16::Day16>("day16example"), Answer::Int(45));
+}
+
+#[test]
+fn day16example2() {
+    assert_eq!(part1::<day16::Day16>("day16example2"), Answer::Int(11048));
+    assert_eq!(part2::<day16::Day16>("day16example2"), Answer::Int(64));
+}
+
+#[test]
+fn day17example() {
+    assert_eq!(
+        part1::<day17::Day17>("day17example"),
+        Answer::from("4,6,3,5,6,3,5,2,1,0")
+    );
+}
+
+#[test]
+fn day17example2() {
+    assert_eq!(part2::<day17::Day17>("day17example2"), Answer::Int(117440));
+}
+
+#[test]
+fn day18example() {
+    assert_eq!(part1::<day18::Day18>("day18example"), Answer::Int(22));
+    assert_eq!(part2::<day18::Day18>("day18example"), Answer::from("6,1"));
+}
+
+#[test]
+fn day19example() {
+    assert_eq!(part1::<day19::Day19>("day19example"), Answer::Int(6));
+    assert_eq!(part2::<day19::Day19>("day19example"), Answer::Int(16));
+}
+
+// The puzzle lists how many cheats save each amount of time; these are the
+// totals of that list for the thresholds in day20example.toml.
+#[test]
+fn day20example() {
+    assert_eq!(part1::<day20::Day20>("day20example"), Answer::Int(44));
+    assert_eq!(part2::<day20::Day20>("day20example"), Answer::Int(285));
+}
+
+#[test]
+fn day21example() {
+    assert_eq!(part1::<day21::Day21>("day21example"), Answer::Int(126384));
+}
+
+#[test]
+fn day21sequence_lengths() {
+    let mut costs = Default::default();
+    let lengths: Vec<_> = ["029A", "980A", "179A", "456A", "379A"]
+        .iter()
+        .map(|code| day21::sequence_length(&mut costs, code, 2))
+        .collect();
+    assert_eq!(lengths, [68, 60, 68, 64, 64]);
+    assert_eq!(day21::sequence_length(&mut costs, "029A", 0), 12);
+}
+
+#[test]
+fn day22example() {
+    assert_eq!(part1::<day22::Day22>("day22example"), Answer::Int(37327623));
+}
+
+#[test]
+fn day22example2() {
+    assert_eq!(part2::<day22::Day22>("day22example2"), Answer::Int(23));
+}
+
+#[test]
+fn day23example() {
+    assert_eq!(part1::<day23::Day23>("day23example"), Answer::Int(7));
+    assert_eq!(
+        part2::<day23::Day23>("day23example"),
+        Answer::from("co,de,ka,ta")
+    );
+}
+
+#[test]
+fn day24example() {
+    assert_eq!(part1::<day24::Day24>("day24example"), Answer::Int(2024));
+}
+
+#[test]
+fn day24example2() {
+    assert_eq!(part1::<day24::Day24>("day24example2"), Answer::Int(4));
+}
+
+#[test]
+fn day25example() {
+    assert_eq!(part1::<day25::Day25>("day25example"), Answer::Int(3));
+}
@@ -0,0 +1,29 @@
+use aoc24::geometry::{Direction, Point};
+
+#[test]
+fn vector_arithmetic() {
+    let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+    assert_eq!(a + b, Point::new(5, 0));
+    assert_eq!(a - b, Point::new(-3, 4));
+    assert_eq!(a * 3, Point::new(3, 6));
+    assert_eq!(-a, Point::new(-1, -2));
+}
+
+#[test]
+fn distances() {
+    let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+}
+
+#[test]
+fn turning() {
+    for d in Direction::ALL {
+        assert_eq!(d.turn_right().turn_left(), d);
+        assert_eq!(d.turn_right().turn_right(), d.reverse());
+        assert_eq!(d.delta() + d.reverse().delta(), Point::ZERO);
+        assert_eq!(Direction::from_arrow(d.arrow()), Some(d));
+    }
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.delta(), Point::new(0, -1));
+}
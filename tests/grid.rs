@@ -0,0 +1,49 @@
+use aoc24::geometry::Point;
+use aoc24::grid::Grid;
+
+const INPUT: &str = "ab#\n.#c\n";
+
+#[test]
+fn parse_and_render() {
+    let grid = Grid::parse(INPUT).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 'c');
+    assert_eq!(grid.to_string(), INPUT);
+}
+
+#[test]
+fn parse_rejects_ragged_lines() {
+    assert!(Grid::parse("abc\nab\n").is_err());
+}
+
+#[test]
+fn checked_access() {
+    let mut grid = Grid::parse(INPUT).unwrap();
+    assert_eq!(grid.get(Point::new(1, 0)), Some(&'b'));
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    *grid.get_mut(Point::new(0, 1)).unwrap() = 'x';
+    assert_eq!(grid[Point::new(0, 1)], 'x');
+}
+
+#[test]
+fn neighbors_stay_in_the_grid() {
+    let grid = Grid::new(3, 3, 0);
+    let mut corner: Vec<_> = grid.neighbors4(Point::ZERO).collect();
+    corner.sort();
+    assert_eq!(corner, [Point::new(0, 1), Point::new(1, 0)]);
+    assert_eq!(grid.neighbors4(Point::new(1, 1)).count(), 4);
+    assert_eq!(grid.neighbors8(Point::new(2, 0)).count(), 3);
+    assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+}
+
+#[test]
+fn find_rows_and_columns() {
+    let grid = Grid::parse(INPUT).unwrap();
+    assert_eq!(grid.find(&'#'), Some(Point::new(2, 0)));
+    assert_eq!(grid.find(&'z'), None);
+    let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+    assert_eq!(rows, ["ab#", ".#c"]);
+    let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+    assert_eq!(columns, ["a.", "b#", "#c"]);
+}
@@ -0,0 +1,64 @@
+// Inputs that parse, but that the solvers used to panic on.
+
+use aoc24::answer::Answer;
+use aoc24::days::*;
+use aoc24::params::Params;
+use aoc24::solution::Solution;
+
+fn answers<S: Solution>(input: &str) -> (Answer, Answer) {
+    answers_with::<S>(input, &Params::default())
+}
+
+fn answers_with<S: Solution>(input: &str, params: &Params) -> (Answer, Answer) {
+    let input = S::parse_with(input, params).unwrap();
+    (S::part1(&input).into(), S::part2(&input).into())
+}
+
+#[test]
+fn empty_inputs_have_no_answers() {
+    assert_eq!(answers::<day9::Day9>(""), (Answer::Int(0), Answer::Int(0)));
+    assert_eq!(
+        answers::<day23::Day23>(""),
+        (Answer::Int(0), Answer::Unsolved)
+    );
+}
+
+#[test]
+fn disk_without_free_space_is_already_compact() {
+    assert_eq!(answers::<day9::Day9>("1"), (Answer::Int(0), Answer::Int(0)));
+    assert_eq!(answers::<day9::Day9>("3"), (Answer::Int(0), Answer::Int(0)));
+}
+
+#[test]
+fn circuits_that_are_not_adders_have_no_swaps() {
+    let input = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/data/day24example.txt"
+    ))
+    .unwrap();
+    assert_eq!(answers::<day24::Day24>(&input).1, Answer::Unsolved);
+}
+
+#[test]
+fn blocked_exits_have_no_path() {
+    let mut params = Params::default();
+    params.set("width", 3);
+    params.set("height", 3);
+    params.set("bytes", 2);
+    assert_eq!(
+        answers_with::<day18::Day18>("0,1\n1,0\n", &params).0,
+        Answer::Unsolved
+    );
+    assert_eq!(
+        answers::<day20::Day20>("#####\n#S#E#\n#####"),
+        (Answer::Unsolved, Answer::Unsolved)
+    );
+}
+
+#[test]
+fn unreachable_ends_have_no_score() {
+    assert_eq!(
+        answers::<day16::Day16>("#####\n#S#E#\n#####"),
+        (Answer::Unsolved, Answer::Unsolved)
+    );
+}
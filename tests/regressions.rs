@@ -0,0 +1,82 @@
+// The puzzles don't publish answers for these examples, or for these parts
+// of them, so the expected values were computed by the solvers themselves.
+// They don't show that a solver is right, only that its answers haven't
+// changed. The published answers are checked in examples.rs.
+
+mod common;
+
+use aoc24::answer::Answer;
+use aoc24::days::*;
+use common::{part1, part2};
+
+#[test]
+fn day9example2_regression() {
+    assert_eq!(part1::<day9::Day9>("day9example2"), Answer::Int(60));
+    assert_eq!(part2::<day9::Day9>("day9example2"), Answer::Int(132));
+}
+
+#[test]
+fn day11example_part2_regression() {
+    assert_eq!(
+        part2::<day11::Day11>("day11example"),
+        Answer::Int(65601038650482)
+    );
+}
+
+#[test]
+fn day12example4_part1_regression() {
+    assert_eq!(part1::<day12::Day12>("day12example4"), Answer::Int(692));
+}
+
+#[test]
+fn day12example5_part1_regression() {
+    assert_eq!(part1::<day12::Day12>("day12example5"), Answer::Int(1184));
+}
+
+#[test]
+fn day13example_part2_regression() {
+    assert_eq!(
+        part2::<day13::Day13>("day13example"),
+        Answer::Int(875318608908)
+    );
+}
+
+#[test]
+fn day15example1_part2_regression() {
+    assert_eq!(part2::<day15::Day15>("day15example1"), Answer::Int(1751));
+}
+
+#[test]
+fn day15example2_regression() {
+    assert_eq!(part1::<day15::Day15>("day15example2"), Answer::Int(908));
+    assert_eq!(part2::<day15::Day15>("day15example2"), Answer::Int(618));
+}
+
+#[test]
+fn day17example2_part1_regression() {
+    assert_eq!(
+        part1::<day17::Day17>("day17example2"),
+        Answer::from("5,7,3,0")
+    );
+}
+
+#[test]
+fn day21example_part2_regression() {
+    assert_eq!(
+        part2::<day21::Day21>("day21example"),
+        Answer::Int(154115708116294)
+    );
+}
+
+#[test]
+fn day22example_part2_regression() {
+    assert_eq!(part2::<day22::Day22>("day22example"), Answer::Int(24));
+}
+
+#[test]
+fn day22example2_part1_regression() {
+    assert_eq!(
+        part1::<day22::Day22>("day22example2"),
+        Answer::Int(37990510)
+    );
+}
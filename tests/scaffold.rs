@@ -0,0 +1,78 @@
+use aoc24::scaffold;
+use std::fs;
+use std::path::PathBuf;
+
+const MOD_RS: &str = "\
+use crate::solution::Day;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+//pub mod day3;
+
+lazy_static! {
+    static ref DAYS: Vec<Day> = vec![
+        Day::new::<day1::Day1>(1),
+        Day::new::<day2::Day2>(2),
+        //Day::new::<day3::Day3>(3),
+        Day::new::<day10::Day10>(10),
+    ];
+}
+";
+
+// A throwaway source tree with just the files that `new_day` changes.
+fn tree(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc24-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for dir in ["src/days", "tests", "data"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+    fs::write(root.join("tests/examples.rs"), "// examples\n").unwrap();
+    root
+}
+
+#[test]
+fn new_day_is_registered_in_order() {
+    let root = tree("new-day");
+    let changed = scaffold::new_day(&root, 4).unwrap();
+    assert_eq!(changed.len(), 5);
+
+    let mod_rs = fs::read_to_string(root.join("src/days/mod.rs")).unwrap();
+    assert!(mod_rs.contains("pub mod day2;\npub mod day4;\n"));
+    assert!(mod_rs.contains(
+        "        //Day::new::<day3::Day3>(3),\n        \
+         Day::new::<day4::Day4>(4),\n        \
+         Day::new::<day10::Day10>(10),\n"
+    ));
+    let source = fs::read_to_string(root.join("src/days/day4.rs")).unwrap();
+    assert!(source.contains("impl Solution for Day4 {"));
+    let tests = fs::read_to_string(root.join("tests/examples.rs")).unwrap();
+    assert!(tests.contains("fn day4example() {"));
+    assert_eq!(
+        fs::read_to_string(root.join("data/day4example.txt")).unwrap(),
+        ""
+    );
+
+    assert!(scaffold::new_day(&root, 4).is_err());
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn commented_out_day_is_restored() {
+    let root = tree("commented-day");
+    scaffold::new_day(&root, 3).unwrap();
+    let mod_rs = fs::read_to_string(root.join("src/days/mod.rs")).unwrap();
+    assert!(mod_rs.contains("\npub mod day3;\n"));
+    assert!(mod_rs.contains("\n        Day::new::<day3::Day3>(3),\n"));
+    assert!(!mod_rs.contains("//"));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn only_days_of_advent_can_be_started() {
+    let root = tree("bad-day");
+    assert!(scaffold::new_day(&root, 26).is_err());
+    assert!(!root.join("src/days/day26.rs").exists());
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,76 @@
+use aoc24::geometry::Point;
+use aoc24::grid::Grid;
+use aoc24::search::{astar, bfs, dijkstra};
+use std::collections::HashSet;
+
+// A weighted graph with two cheapest paths from 'a' to 'e' (a-b-d-e and
+// a-c-d-e, both costing 4) and a more expensive direct edge.
+fn edges(n: &char) -> Vec<(char, u32)> {
+    match n {
+        'a' => vec![('b', 1), ('c', 2), ('e', 10)],
+        'b' => vec![('d', 2)],
+        'c' => vec![('d', 1)],
+        'd' => vec![('e', 1)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn dijkstra_finds_the_cheapest_path() {
+    let search = dijkstra('a', edges, |n| *n == 'e');
+    assert_eq!(search.goal(), Some(&'e'));
+    assert_eq!(search.cost(&'e'), Some(4));
+    let path = search.path(&'e').unwrap();
+    assert!(path == ['a', 'b', 'd', 'e'] || path == ['a', 'c', 'd', 'e']);
+}
+
+#[test]
+fn dijkstra_keeps_every_cheapest_path() {
+    let search = dijkstra('a', edges, |n| *n == 'e');
+    assert_eq!(
+        search.on_paths(['e']),
+        HashSet::from(['a', 'b', 'c', 'd', 'e'])
+    );
+    assert_eq!(search.on_paths(['d']), HashSet::from(['a', 'b', 'c', 'd']));
+}
+
+#[test]
+fn dijkstra_without_a_goal_searches_everything() {
+    let search = dijkstra('a', edges, |_| false);
+    assert_eq!(search.goal(), None);
+    assert_eq!(search.costs().len(), 5);
+    assert_eq!(search.cost(&'z'), None);
+    assert_eq!(search.path(&'z'), None);
+}
+
+const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+";
+
+#[test]
+fn bfs_and_astar_agree_on_a_maze() {
+    let maze = Grid::parse(MAZE).unwrap();
+    let open = |p: &Point| {
+        maze.neighbors4(*p)
+            .filter(|n| maze[*n] != '#')
+            .collect::<Vec<_>>()
+    };
+    let end = Point::new(6, 0);
+
+    let by_bfs = bfs(Point::ZERO, open);
+    assert_eq!(by_bfs.cost(&end), Some(12));
+    assert_eq!(by_bfs.path(&end).unwrap().len(), 13);
+    assert_eq!(by_bfs.cost(&Point::new(2, 0)), None);
+
+    let by_astar = astar(
+        Point::ZERO,
+        |p| open(p).into_iter().map(|n| (n, 1)),
+        |p| p.manhattan(end) as usize,
+        |p| *p == end,
+    );
+    assert_eq!(by_astar.cost(&end), Some(12));
+    assert_eq!(by_astar.path(&end), by_bfs.path(&end));
+}
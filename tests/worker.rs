@@ -0,0 +1,81 @@
+use aoc24::runner::PANIC_PART;
+use std::process::{Command, Output};
+
+// Run the aoc24 binary, which solves each part in a worker process of its
+// own when given a timeout.
+fn aoc24(args: &[&str]) -> Output {
+    command(args).output().unwrap()
+}
+
+fn command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_aoc24"));
+    command.current_dir(env!("CARGO_MANIFEST_DIR")).args(args);
+    command
+}
+
+#[test]
+fn parts_within_the_timeout_are_solved() {
+    let output = aoc24(&["day7", "data/day7example.txt", "--timeout", "60"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Part 1: 3749\nPart 2: 11387\n"));
+}
+
+#[test]
+fn slow_parts_time_out() {
+    let output = aoc24(&["day22", "data/day22.txt", "--part", "2", "--timeout", "0.1"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Part 2: TIMEOUT\n"));
+}
+
+#[test]
+fn panics_are_reported_per_part() {
+    let args = [
+        "day7",
+        "data/day7example.txt",
+        "--timeout",
+        "60",
+        "--format",
+        "json",
+    ];
+    let output = command(&args).env(PANIC_PART, "2").output().unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r#"{"answer":3749,"error":null,"part":1,"#));
+    assert!(stdout.contains(r#""error":"part 2 panicked: AOC24_PANIC_PART is set to 2""#));
+}
+
+#[test]
+fn workers_use_the_same_number_of_jobs() {
+    let args = [
+        "-v",
+        "--jobs",
+        "1",
+        "day7",
+        "data/day7example.txt",
+        "--timeout",
+        "60",
+    ];
+    let output = aoc24(&args);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let workers: Vec<&str> = stderr
+        .lines()
+        .filter(|l| l.contains("Starting worker"))
+        .collect();
+    assert_eq!(workers.len(), 2);
+    assert!(workers.iter().all(|l| l.contains(r#""--jobs" "1""#)));
+}
+
+#[test]
+fn panics_without_a_timeout_are_errors() {
+    let output = command(&["day7", "data/day7example.txt"])
+        .env(PANIC_PART, "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Error: panicked: AOC24_PANIC_PART is set to 1\n"));
+    assert!(!stderr.contains("backtrace"));
+}